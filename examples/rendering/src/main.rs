@@ -24,16 +24,21 @@ use std::path::Path;
 use std::fs::{self, DirEntry};
 
 use glium::texture::{CompressedSrgbTexture2d, RawImage2d};
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerWrapFunction};
+use glium::{Blend, BlendingFunction, LinearBlendingFactor};
+
+use libspine_sys::spBlendMode::SP_BLEND_MODE_NORMAL;
 use spiner::animation::state::{State as AnimationState, StateData};
-use spiner::atlas::{page::Page, Atlas};
+use spiner::atlas::page::{Filter, Page, Wrap};
+use spiner::atlas::Atlas;
 use spiner::attachment::vertex::Vertex as VertexAttachment;
 use spiner::attachment::Attachment;
+use spiner::cache::AssetCache;
 use spiner::extension::Texture;
-use spiner::skeleton::json::Json as SkeletonJson;
+use spiner::render::{BlendFactor, BlendFunc};
 use spiner::skeleton::data::Data as SkeletonData;
 use spiner::skeleton::Skeleton;
-
-const MAX_VERTICES: usize = 1000;
+use spiner::Error as SpinerError;
 
 #[derive(Copy, Clone, Debug)]
 struct Vertex {
@@ -51,16 +56,69 @@ fn read_file(path: &str) -> Result<Vec<u8>, Error> {
     Ok(buf)
 }
 
-fn read_texture(path: &str) -> Result<Texture, Error> {
-    println!("read texture {}", path);
-    let image = image::open(path).unwrap().to_rgba();
+fn read_texture(path: &str, texture: &mut Texture) -> Result<(), SpinerError> {
+    println!("read texture {} ({:?})", path, texture.format);
+    let image = image::open(path)
+        .map_err(|err| SpinerError::TextureLoad {
+            path: path.to_owned(),
+            message: err.to_string(),
+        })?
+        .to_rgba();
     let (width, height) = image.dimensions();
+    texture.buffer = image.into_raw();
+    texture.width = width;
+    texture.height = height;
+
+    Ok(())
+}
+
+fn minify_filter(filter: Filter) -> MinifySamplerFilter {
+    match filter {
+        Filter::Nearest => MinifySamplerFilter::Nearest,
+        Filter::MipMapNearestNearest => MinifySamplerFilter::NearestMipmapNearest,
+        Filter::MipMapLinearNearest => MinifySamplerFilter::LinearMipmapNearest,
+        Filter::MipMapNearestLinear => MinifySamplerFilter::NearestMipmapLinear,
+        Filter::MipMap | Filter::MipMapLinearLinear => MinifySamplerFilter::LinearMipmapLinear,
+        Filter::Linear | Filter::Unknown => MinifySamplerFilter::Linear,
+    }
+}
+
+fn magnify_filter(filter: Filter) -> MagnifySamplerFilter {
+    match filter {
+        Filter::Nearest => MagnifySamplerFilter::Nearest,
+        _ => MagnifySamplerFilter::Linear,
+    }
+}
 
-    Ok(Texture {
-        buffer: image.into_raw(),
-        width: width,
-        height: height,
-    })
+fn blend(func: BlendFunc) -> Blend {
+    let factor = |factor: BlendFactor| match factor {
+        BlendFactor::Zero => LinearBlendingFactor::Zero,
+        BlendFactor::One => LinearBlendingFactor::One,
+        BlendFactor::SrcColor => LinearBlendingFactor::SourceColor,
+        BlendFactor::OneMinusSrcColor => LinearBlendingFactor::OneMinusSourceColor,
+        BlendFactor::SrcAlpha => LinearBlendingFactor::SourceAlpha,
+        BlendFactor::OneMinusSrcAlpha => LinearBlendingFactor::OneMinusSourceAlpha,
+        BlendFactor::DstColor => LinearBlendingFactor::DestinationColor,
+    };
+    let function = BlendingFunction::Addition {
+        source: factor(func.src),
+        destination: factor(func.dst),
+    };
+
+    Blend {
+        color: function,
+        alpha: function,
+        ..Default::default()
+    }
+}
+
+// glium applies a single wrap function to every axis
+fn wrap_function(wrap: Wrap) -> SamplerWrapFunction {
+    match wrap {
+        Wrap::MirroredRepeat => SamplerWrapFunction::Mirror,
+        Wrap::ClampToEdge => SamplerWrapFunction::Clamp,
+        Wrap::Repeat => SamplerWrapFunction::Repeat,
+    }
 }
 
 extend_spine!({
@@ -70,7 +128,7 @@ extend_spine!({
 
 struct Asset {
     pub name: String,
-    atlas: Atlas,
+    atlas: Rc<Atlas>,
     skeleton_data: Rc<SkeletonData>,
     state_data: Rc<StateData>,
     skeleton: Skeleton,
@@ -78,11 +136,12 @@ struct Asset {
 }
 
 impl Asset {
-    pub fn load(name: &str, path: &str) -> Result<Self, Error> {
-        let atlas = Atlas::from_file(&Asset::atlas_path(name, path))?;
-        let skeleton_data = Rc::new(
-            SkeletonJson::new(&atlas, 1.)?.read_skeleton_file(&Asset::skeleton_path(name, path))?
-        );
+    pub fn load(name: &str, path: &str, cache: &mut AssetCache) -> Result<Self, SpinerError> {
+        let atlas_path = Asset::atlas_path(name, path);
+        let atlas = cache.atlas(&atlas_path)?;
+        atlas.check_textures()?;
+        let skeleton_data =
+            cache.skeleton_data(&Asset::skeleton_path(name, path), &atlas_path, 1.)?;
         let mut state_data = StateData::from_skeleton_data(Rc::clone(&skeleton_data))?;
         state_data.set_default_mix(0.5);
         let state_data = Rc::new(state_data);
@@ -98,7 +157,7 @@ impl Asset {
                 .nth(rand::thread_rng().gen_range(len - 1, len) as usize)
                 .unwrap(),
             true,
-        );
+        )?;
 
         let mut skeleton = Skeleton::from_data(Rc::clone(&skeleton_data))?;
         skeleton.set_position((0., -500.));
@@ -115,7 +174,7 @@ impl Asset {
 
     pub fn update(&mut self, delta: f32) {
         self.animation_state.update(delta);
-        self.animation_state.apply(&mut self.skeleton);
+        self.animation_state.apply(&mut self.skeleton).unwrap();
         self.skeleton.update_world_transform();
     }
 
@@ -138,6 +197,7 @@ impl Asset {
 
 fn main() -> std::io::Result<()> {
     let assets_dir = Path::new("./assets");
+    let mut cache = AssetCache::new();
     let mut assets: Vec<Asset> = fs::read_dir(assets_dir)?
         .filter_map(|entry| {
             let path = entry.unwrap().path();
@@ -147,11 +207,11 @@ fn main() -> std::io::Result<()> {
 
             path.components().last().map(|comp| comp.as_os_str().to_string_lossy().into_owned())
         })
-        .filter_map(|ref name| Asset::load(name, "./assets").ok())
+        .filter_map(|ref name| Asset::load(name, "./assets", &mut cache).ok())
         .collect();
 
     let mut perspective = [[0.; 3]; 3];
-    let mut world_vertices = vec![0.; MAX_VERTICES];
+    let mut world_vertices = Vec::new();
 
     // setup glium
     let mut events_loop = glium::glutin::EventsLoop::new();
@@ -164,10 +224,6 @@ fn main() -> std::io::Result<()> {
     let vertex_src = include_str!("../gl/spine.vert");
     let fragment_src = include_str!("../gl/spine.frag");
     let program = glium::Program::from_source(&display, vertex_src, fragment_src, None).unwrap();
-    let params = glium::DrawParameters {
-        blend: glium::Blend::alpha_blending(),
-        ..Default::default()
-    };
 
     // Preload textures
     let mut textures = std::collections::HashMap::new();
@@ -187,7 +243,10 @@ fn main() -> std::io::Result<()> {
 
             textures.insert(
                 page.name.clone(),
-                CompressedSrgbTexture2d::new(&display, image).unwrap(),
+                (
+                    CompressedSrgbTexture2d::new(&display, image).unwrap(),
+                    texture.sampler,
+                ),
             );
         }
     }
@@ -207,7 +266,16 @@ fn main() -> std::io::Result<()> {
         for asset in assets.iter() {
             let (page, vertices, indices) = asset.batch(&mut world_vertices);
 
-            if let Some(texture) = page.and_then(|p| textures.get(&p.name)) {
+            let premultiplied_alpha = page
+                .as_ref()
+                .and_then(|page| page.renderer_object())
+                .map_or(false, |texture| texture.premultiplied_alpha);
+            let params = glium::DrawParameters {
+                blend: blend(BlendFunc::new(SP_BLEND_MODE_NORMAL, premultiplied_alpha)),
+                ..Default::default()
+            };
+
+            if let Some(&(ref texture, sampler)) = page.and_then(|p| textures.get(&p.name)) {
                 let vertex_buffer = glium::VertexBuffer::new(&display, &vertices).unwrap();
                 let index_buffer = glium::index::IndexBuffer::new(
                     &display,
@@ -217,6 +285,10 @@ fn main() -> std::io::Result<()> {
                 let uniforms = uniform! {
                     perspective: perspective,
                     tex: texture
+                        .sampled()
+                        .minify_filter(minify_filter(sampler.min_filter))
+                        .magnify_filter(magnify_filter(sampler.mag_filter))
+                        .wrap_function(wrap_function(sampler.u_wrap))
                 };
                 target
                     .draw(&vertex_buffer, &index_buffer, &program, &uniforms, &params)
@@ -257,17 +329,16 @@ fn batch_skeleton_vertices(
         };
 
         let (attachment_indices, uvs) = match attachment {
-            Attachment::Mesh(mesh) => {
-                let len = mesh.world_vertices_len();
-                mesh.compute_world_vertices(slot, 0, len as i32, world_vertices, 0, 2);
+            Attachment::Mesh(mesh) | Attachment::LinkedMesh(mesh) => {
+                mesh.compute_world_vertices_vec(slot, world_vertices);
                 if page.as_ref().is_none() {
                     page = mesh.atlas_region().and_then(|region| region.page());
                 }
 
                 (mesh.triangles(), mesh.uvs())
             }
-            Attachment::Region(mut region) => {
-                region.compute_world_vertices(&mut slot.bone().unwrap(), world_vertices, 0, 2);
+            Attachment::Region(region) => {
+                region.compute_world_vertices_vec(&slot.bone().unwrap(), world_vertices);
                 if page.as_ref().is_none() {
                     page = region.atlas_region().and_then(|region| region.page());
                 }
@@ -0,0 +1,253 @@
+use super::sampler::{self, Sampler};
+use common::from_raw_buf_or_empty;
+use libspine_sys::spTimelineType::*;
+use libspine_sys::*;
+use raw::*;
+use skeleton::data::Data as SkeletonData;
+use skeleton::Skeleton;
+use std::os::raw::c_void;
+use std::rc::Rc;
+
+// How a track entry combines with the tracks below it. spine-c 3.6 has no blend modes of its
+// own, so all but `Replace` are emulated by `State::apply`: the entry is muted while spine-c
+// applies the state, then its pose is sampled alone from the setup pose and blended into the
+// skeleton. Emulated entries follow every spine-c track, a `Replace` entry on a higher track
+// does not override them, and they blend bone transforms only.
+// - `Setup` mixes the bone properties the entry keys from their setup values by its alpha,
+//   ignoring the tracks below.
+// - `First` mixes them from the current pose by its alpha, towards the setup values before
+//   the first key.
+// - `Replace` leaves the entry to spine-c, which mixes the lowest track from the setup pose
+//   and every other track from the current pose.
+// - `Add` adds the entry's offset from the setup pose times its alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MixBlend {
+    Setup,
+    First,
+    #[default]
+    Replace,
+    Add,
+}
+
+// kept in `userData` of the entry, which spine-c never reads
+pub(crate) fn from_user_data(user_data: *mut c_void) -> MixBlend {
+    match user_data as usize {
+        1 => MixBlend::Add,
+        2 => MixBlend::Setup,
+        3 => MixBlend::First,
+        _ => MixBlend::Replace,
+    }
+}
+
+pub(crate) fn to_user_data(blend: MixBlend) -> *mut c_void {
+    let tag: usize = match blend {
+        MixBlend::Replace => 0,
+        MixBlend::Add => 1,
+        MixBlend::Setup => 2,
+        MixBlend::First => 3,
+    };
+
+    tag as *mut c_void
+}
+
+struct Layer {
+    entry: NonNull<spTrackEntry>,
+    blend: MixBlend,
+    alpha: f32,
+    // share of the entry in its track's mix
+    weight: f32,
+}
+
+// Emulated entries are muted while spine-c applies the state, then their poses are sampled
+// and blended into the skeleton.
+#[derive(Default)]
+pub(crate) struct Layering {
+    layers: Vec<Layer>,
+    sampler: Option<Box<Sampler>>,
+}
+
+impl Layering {
+    pub(crate) fn begin(&mut self, state: &spAnimationState) {
+        self.layers.clear();
+        if state.tracks.is_null() {
+            return;
+        }
+
+        for index in 0..state.tracksCount.max(0) as usize {
+            if let Some(entry) = NonNull::new(unsafe { *state.tracks.add(index) }) {
+                self.collect(entry, 1.);
+            }
+        }
+    }
+
+    pub(crate) fn end(&mut self, data: &Rc<SkeletonData>, skeleton: &mut Skeleton) {
+        for layer in self.layers.iter_mut() {
+            unsafe { layer.entry.as_mut().alpha = layer.alpha };
+        }
+        if self.layers.is_empty() {
+            return;
+        }
+
+        if self.sampler.is_none() {
+            self.sampler = Sampler::new(Rc::clone(data)).ok().map(Box::new);
+        }
+        let sampler = match self.sampler {
+            Some(ref mut sampler) => sampler,
+            None => return,
+        };
+
+        for layer in self.layers.iter() {
+            let alpha = layer.alpha * layer.weight;
+            if layer.weight == 0. || (layer.blend == MixBlend::Add && alpha == 0.) {
+                continue;
+            }
+
+            let (animation, time) = unsafe {
+                let entry = layer.entry.as_ptr();
+                ((*entry).animation, spTrackEntry_getAnimationTime(entry))
+            };
+            let keyed = unsafe { keyed(animation) };
+            if let Some(pose) = sampler.pose(animation, time) {
+                match layer.blend {
+                    MixBlend::Add => add_pose(pose, skeleton, alpha),
+                    _ => mix_pose(pose, skeleton, &keyed, layer),
+                }
+            }
+        }
+    }
+
+    fn collect(&mut self, mut entry: NonNull<spTrackEntry>, weight: f32) {
+        let raw = unsafe { entry.as_mut() };
+        let mix = match NonNull::new(raw.mixingFrom) {
+            Some(from) if raw.mixDuration > 0. => {
+                let mix = (raw.mixTime / raw.mixDuration).min(1.);
+                self.collect(from, weight * (1. - mix));
+                mix
+            }
+            _ => 1.,
+        };
+
+        let blend = from_user_data(raw.userData);
+        if blend != MixBlend::Replace {
+            self.layers.push(Layer {
+                entry,
+                blend,
+                alpha: raw.alpha,
+                weight: weight * mix,
+            });
+            raw.alpha = 0.;
+        }
+    }
+}
+
+// adds the offset of every posed bone from its setup pose, as spine's additive timelines do
+fn add_pose(pose: &Skeleton, skeleton: &mut Skeleton, alpha: f32) {
+    for index in 0..pose.as_raw().bonesCount.max(0) as usize {
+        let (from, to) = match (
+            sampler::bone(pose, index),
+            sampler::bone_mut(skeleton, index),
+        ) {
+            (Some(from), Some(to)) => (from, to),
+            _ => continue,
+        };
+        let setup = match unsafe { from.data.as_ref() } {
+            Some(setup) => setup,
+            None => continue,
+        };
+
+        to.x += (from.x - setup.x) * alpha;
+        to.y += (from.y - setup.y) * alpha;
+        to.rotation += sampler::wrap_degrees(from.rotation - setup.rotation) * alpha;
+        to.scaleX += (from.scaleX - setup.scaleX) * alpha;
+        to.scaleY += (from.scaleY - setup.scaleY) * alpha;
+        to.shearX += (from.shearX - setup.shearX) * alpha;
+        to.shearY += (from.shearY - setup.shearY) * alpha;
+    }
+}
+
+// blends `Setup` and `First` entries into the properties their animation keys, before the
+// first key the sampled pose holds the setup values
+fn mix_pose(pose: &Skeleton, skeleton: &mut Skeleton, keyed: &[Keyed], layer: &Layer) {
+    let from_setup = layer.blend == MixBlend::Setup;
+    let (alpha, weight) = (layer.alpha, layer.weight);
+    let mix = |current: f32, sampled: f32, setup: f32| {
+        let base = if from_setup { setup } else { current };
+        let target = base + (sampled - base) * alpha;
+        current + (target - current) * weight
+    };
+    let mix_rotation = |current: f32, sampled: f32, setup: f32| {
+        let base = if from_setup { setup } else { current };
+        let target = base + sampler::wrap_degrees(sampled - base) * alpha;
+        current + sampler::wrap_degrees(target - current) * weight
+    };
+
+    for (index, keyed) in keyed.iter().enumerate() {
+        let (from, to) = match (
+            sampler::bone(pose, index),
+            sampler::bone_mut(skeleton, index),
+        ) {
+            (Some(from), Some(to)) => (from, to),
+            _ => continue,
+        };
+        let setup = match unsafe { from.data.as_ref() } {
+            Some(setup) => setup,
+            None => continue,
+        };
+
+        if keyed.rotate {
+            to.rotation = mix_rotation(to.rotation, from.rotation, setup.rotation);
+        }
+        if keyed.translate {
+            to.x = mix(to.x, from.x, setup.x);
+            to.y = mix(to.y, from.y, setup.y);
+        }
+        if keyed.scale {
+            to.scaleX = mix(to.scaleX, from.scaleX, setup.scaleX);
+            to.scaleY = mix(to.scaleY, from.scaleY, setup.scaleY);
+        }
+        if keyed.shear {
+            to.shearX = mix(to.shearX, from.shearX, setup.shearX);
+            to.shearY = mix(to.shearY, from.shearY, setup.shearY);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Keyed {
+    rotate: bool,
+    translate: bool,
+    scale: bool,
+    shear: bool,
+}
+
+// bone timelines of the animation, indexed by bone
+unsafe fn keyed(animation: *const spAnimation) -> Vec<Keyed> {
+    let mut keyed: Vec<Keyed> = Vec::new();
+    let animation = match animation.as_ref() {
+        Some(animation) => animation,
+        None => return keyed,
+    };
+
+    let timelines = from_raw_buf_or_empty(animation.timelines, animation.timelinesCount);
+    for &timeline in timelines.iter().filter(|timeline| !timeline.is_null()) {
+        let type_ = (*timeline).type_;
+        match type_ {
+            SP_TIMELINE_ROTATE | SP_TIMELINE_TRANSLATE | SP_TIMELINE_SCALE | SP_TIMELINE_SHEAR => {}
+            _ => continue,
+        }
+
+        // all four are `spBaseTimeline`s
+        let index = (*(timeline as *const spBaseTimeline)).boneIndex.max(0) as usize;
+        if index >= keyed.len() {
+            keyed.resize(index + 1, Keyed::default());
+        }
+        match type_ {
+            SP_TIMELINE_ROTATE => keyed[index].rotate = true,
+            SP_TIMELINE_TRANSLATE => keyed[index].translate = true,
+            SP_TIMELINE_SCALE => keyed[index].scale = true,
+            _ => keyed[index].shear = true,
+        }
+    }
+
+    keyed
+}
@@ -0,0 +1,112 @@
+use common::to_string;
+use event::Event;
+use extension::catch_panic;
+use libspine_sys::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::vec::Drain;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    Start,
+    Interrupt,
+    End,
+    Complete,
+    Event,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationEvent {
+    pub kind: EventType,
+    pub track_index: i32,
+    pub animation: Rc<str>,
+    // set for `EventType::Event` only
+    pub event: Option<Rc<Event>>,
+}
+
+// Filled by the `spAnimationState` listener. Animations and events live as long as the
+// skeleton data, so their names and payloads are converted once and shared afterwards;
+// together with the reused buffer, draining does not allocate once every event was seen.
+#[derive(Default)]
+pub(crate) struct EventQueue {
+    events: Vec<AnimationEvent>,
+    animations: HashMap<*const spAnimation, Rc<str>>,
+    payloads: HashMap<*const spEvent, Rc<Event>>,
+}
+
+impl EventQueue {
+    pub(crate) fn drain(&mut self) -> Drain<'_, AnimationEvent> {
+        self.events.drain(..)
+    }
+
+    pub(crate) fn pending(&self) -> &[AnimationEvent] {
+        &self.events
+    }
+
+    pub(crate) fn set_pending(&mut self, events: &[AnimationEvent]) {
+        self.events.clear();
+        self.events.extend_from_slice(events);
+    }
+
+    pub(crate) fn replace_with(&mut self, other: &mut EventQueue) {
+        self.events.clear();
+        self.events.append(&mut other.events);
+    }
+
+    fn push(&mut self, kind: EventType, entry: &spTrackEntry, event: *const spEvent) {
+        let animation = Rc::clone(
+            self.animations
+                .entry(entry.animation as *const _)
+                .or_insert_with(|| unsafe { animation_name(entry.animation) }),
+        );
+        let event = unsafe { event.as_ref() }.map(|raw_ref| {
+            Rc::clone(
+                self.payloads
+                    .entry(event)
+                    .or_insert_with(|| Rc::new(Event::from(raw_ref))),
+            )
+        });
+
+        self.events.push(AnimationEvent {
+            kind,
+            track_index: entry.trackIndex,
+            animation,
+            event,
+        });
+    }
+}
+
+unsafe fn animation_name(animation: *const spAnimation) -> Rc<str> {
+    let name = animation
+        .as_ref()
+        .map(|animation| to_string(animation.name))
+        .unwrap_or_default();
+
+    Rc::from(name)
+}
+
+// `rendererObject` of the animation state points to its `EventQueue`
+pub(crate) unsafe extern "C" fn listener(
+    state: *mut spAnimationState,
+    type_: spEventType,
+    entry: *mut spTrackEntry,
+    event: *mut spEvent,
+) {
+    let kind = match type_ {
+        spEventType::SP_ANIMATION_START => EventType::Start,
+        spEventType::SP_ANIMATION_INTERRUPT => EventType::Interrupt,
+        spEventType::SP_ANIMATION_END => EventType::End,
+        spEventType::SP_ANIMATION_COMPLETE => EventType::Complete,
+        spEventType::SP_ANIMATION_EVENT => EventType::Event,
+        // the entry is about to be freed, nothing to report
+        spEventType::SP_ANIMATION_DISPOSE => return,
+    };
+
+    let queue = state
+        .as_ref()
+        .and_then(|state| (state.rendererObject as *mut EventQueue).as_mut());
+    if let (Some(queue), Some(entry)) = (queue, entry.as_ref()) {
+        // resumed by `State` once spine-c returns
+        catch_panic(|| queue.push(kind, entry, event));
+    }
+}
@@ -2,7 +2,13 @@ use libspine_sys::spAnimation;
 use std::ffi::CStr;
 use std::os::raw::c_char;
 
+pub mod blend;
+pub mod events;
+pub mod root_motion;
+mod sampler;
+pub mod snapshot;
 pub mod state;
+pub mod track_entry;
 
 #[derive(Debug, Clone)]
 pub struct Animation {
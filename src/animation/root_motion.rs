@@ -0,0 +1,288 @@
+use super::sampler::{self, Sampler};
+use super::state::State;
+use super::track_entry::TrackEntry;
+use common::from_raw_buf_or_empty;
+use libspine_sys::spTimelineType::*;
+use libspine_sys::*;
+use raw::*;
+use skeleton::data::Data as SkeletonData;
+use skeleton::Skeleton;
+use std::collections::HashMap;
+use std::mem;
+use std::rc::Rc;
+
+// spine-c reuses track entry memory, so entries are told apart by their animation as well
+type EntryKey = (*const spTrackEntry, *const spAnimation);
+
+// translation in skeleton space and rotation in degrees, counterclockwise
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Motion {
+    pub x: f32,
+    pub y: f32,
+    pub rotation: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    x: f32,
+    y: f32,
+    rotation: f32,
+}
+
+impl Sample {
+    const ZERO: Sample = Sample {
+        x: 0.,
+        y: 0.,
+        rotation: 0.,
+    };
+
+    fn delta(&self, from: &Sample) -> Sample {
+        Sample {
+            x: self.x - from.x,
+            y: self.y - from.y,
+            rotation: sampler::wrap_degrees(self.rotation - from.rotation),
+        }
+    }
+
+    fn add(&self, other: &Sample, times: f32) -> Sample {
+        Sample {
+            x: self.x + other.x * times,
+            y: self.y + other.y * times,
+            rotation: self.rotation + other.rotation * times,
+        }
+    }
+
+    fn lerp(&self, to: &Sample, translate_alpha: f32, rotate_alpha: f32) -> Sample {
+        Sample {
+            x: self.x + (to.x - self.x) * translate_alpha,
+            y: self.y + (to.y - self.y) * translate_alpha,
+            rotation: self.rotation + (to.rotation - self.rotation) * rotate_alpha,
+        }
+    }
+}
+
+// Moves the translation (and optionally rotation) of one bone out of the animated pose so it
+// can drive the entity instead. Call `extract` once per frame, after `State::apply` and before
+// `Skeleton::update_world_transform`.
+//
+// Every animation on the state is sampled on a private skeleton, so looping is accounted for
+// across the wrap and tracks and mixes are combined with the alphas spine-c uses for translate
+// and rotate timelines. Entries without a timeline for the bone leave the mixed motion as is.
+pub struct RootMotion {
+    pub extract_x: bool,
+    pub extract_y: bool,
+    pub extract_rotation: bool,
+    bone_index: usize,
+    setup: Sample,
+    sampler: Sampler,
+    // track time of every entry at the last call
+    track_times: HashMap<EntryKey, f32>,
+    previous_track_times: HashMap<EntryKey, f32>,
+    started: bool,
+}
+
+impl RootMotion {
+    pub fn new(data: Rc<SkeletonData>, bone: &str) -> Result<Self, Error> {
+        let bone_data = data
+            .find_bone(bone)
+            .ok_or_else(|| Error::BoneNotFound(bone.to_owned()))?;
+        let sampler = Sampler::new(data)?;
+
+        Ok(RootMotion {
+            extract_x: true,
+            extract_y: true,
+            extract_rotation: false,
+            bone_index: bone_data.index as usize,
+            setup: Sample {
+                x: bone_data.x,
+                y: bone_data.y,
+                rotation: bone_data.rotation,
+            },
+            sampler,
+            track_times: HashMap::new(),
+            previous_track_times: HashMap::new(),
+            started: false,
+        })
+    }
+
+    // The skeleton has to be created from the same data. Extracted components of the bone are
+    // reset to the setup pose, the motion since the previous call is returned.
+    pub fn extract(&mut self, state: &State, skeleton: &mut Skeleton) -> Motion {
+        mem::swap(&mut self.track_times, &mut self.previous_track_times);
+        self.track_times.clear();
+
+        let mut total = Sample::ZERO;
+        for entry in state.tracks() {
+            total = self.blend(&entry, total, 1.);
+        }
+        self.started = true;
+
+        let flip = {
+            let raw = skeleton.as_raw();
+            (raw.flipX != 0, raw.flipY != 0)
+        };
+        let bone = match sampler::bone_mut(skeleton, self.bone_index) {
+            Some(bone) => bone,
+            None => return Motion::default(),
+        };
+
+        if self.extract_x {
+            bone.x = self.setup.x;
+        } else {
+            total.x = 0.;
+        }
+        if self.extract_y {
+            bone.y = self.setup.y;
+        } else {
+            total.y = 0.;
+        }
+        if self.extract_rotation {
+            bone.rotation = self.setup.rotation;
+        } else {
+            total.rotation = 0.;
+        }
+
+        to_skeleton_space(bone, flip, &total)
+    }
+
+    // forgets the previous track times, the next call reports no motion for running entries
+    pub fn reset(&mut self) {
+        self.track_times.clear();
+        self.started = false;
+    }
+
+    // mixing as in `spAnimationState_apply`, the entry being mixed out fades with `1 - mix`
+    fn blend(&mut self, entry: &TrackEntry, base: Sample, alpha: f32) -> Sample {
+        let (base, mix) = match entry.mixing_from() {
+            Some(from) => {
+                let mix = if entry.mix_duration() > 0. {
+                    (entry.mix_time() / entry.mix_duration()).min(1.)
+                } else {
+                    1.
+                };
+                (self.blend(&from, base, alpha * (1. - mix)), mix)
+            }
+            None => (base, 1.),
+        };
+
+        let motion = self.entry_motion(entry);
+        let alpha = entry.alpha() * mix * alpha;
+        // spine-c leaves the bone alone without a timeline, so such entries do not weigh in
+        let (translate, rotate) = unsafe { keyed(entry.as_raw().animation, self.bone_index) };
+        base.lerp(
+            &motion,
+            if translate { alpha } else { 0. },
+            if rotate { alpha } else { 0. },
+        )
+    }
+
+    fn entry_motion(&mut self, entry: &TrackEntry) -> Sample {
+        let raw = entry.as_raw();
+        let key = (
+            raw as *const spTrackEntry,
+            raw.animation as *const spAnimation,
+        );
+        let time = raw.trackTime.max(0.);
+        self.track_times.insert(key, time);
+
+        let last = match self.previous_track_times.get(&key) {
+            Some(last) => last.min(time),
+            // entries started since the last call moved from their beginning
+            None if self.started => 0.,
+            None => time,
+        };
+        if last == time || raw.animation.is_null() {
+            return Sample::ZERO;
+        }
+
+        let (start, end) = (raw.animationStart, raw.animationEnd);
+        let duration = end - start;
+        if raw.loop_ == 0 || duration <= 0. {
+            let from = self.sample(raw.animation, (start + last).min(end));
+            let to = self.sample(raw.animation, (start + time).min(end));
+            return to.delta(&from);
+        }
+
+        let from = self.sample(raw.animation, start + last % duration);
+        let to = self.sample(raw.animation, start + time % duration);
+        let loops = (time / duration).floor() - (last / duration).floor();
+        if loops < 1. {
+            return to.delta(&from);
+        }
+
+        // to the end of the loop, every full loop in between, and from the start again
+        let first = self.sample(raw.animation, start);
+        let last = self.sample(raw.animation, end);
+        let cycle = last.delta(&first);
+
+        last.delta(&from)
+            .add(&cycle, loops - 1.)
+            .add(&to.delta(&first), 1.)
+    }
+
+    fn sample(&mut self, animation: *const spAnimation, time: f32) -> Sample {
+        let index = self.bone_index;
+        let bone = self
+            .sampler
+            .pose(animation, time)
+            .and_then(|skeleton| sampler::bone(skeleton, index));
+
+        match bone {
+            Some(bone) => Sample {
+                x: bone.x,
+                y: bone.y,
+                rotation: bone.rotation,
+            },
+            None => self.setup,
+        }
+    }
+}
+
+// whether the animation has a translate and a rotate timeline for the bone
+unsafe fn keyed(animation: *const spAnimation, bone_index: usize) -> (bool, bool) {
+    let animation = match animation.as_ref() {
+        Some(animation) => animation,
+        None => return (false, false),
+    };
+    let timelines = from_raw_buf_or_empty(animation.timelines, animation.timelinesCount);
+
+    let mut keyed = (false, false);
+    for &timeline in timelines.iter().filter(|timeline| !timeline.is_null()) {
+        // translate and rotate timelines are both `spBaseTimeline`s
+        let keys_bone = || (*(timeline as *const spBaseTimeline)).boneIndex == bone_index as i32;
+        match (*timeline).type_ {
+            SP_TIMELINE_TRANSLATE if keys_bone() => keyed.0 = true,
+            SP_TIMELINE_ROTATE if keys_bone() => keyed.1 = true,
+            _ => {}
+        }
+    }
+
+    keyed
+}
+
+// the parent's world transform is the previous frame's, which is what the delta was moved in
+fn to_skeleton_space(bone: &spBone, flip: (bool, bool), local: &Sample) -> Motion {
+    let parent = unsafe { bone.parent.as_ref() };
+    let (x, y, mirrored) = match parent {
+        Some(parent) => (
+            parent.a * local.x + parent.b * local.y,
+            parent.c * local.x + parent.d * local.y,
+            parent.a * parent.d - parent.b * parent.c < 0.,
+        ),
+        None => (
+            if flip.0 { -local.x } else { local.x },
+            if flip.1 { -local.y } else { local.y },
+            flip.0 != flip.1,
+        ),
+    };
+
+    Motion {
+        x,
+        y,
+        rotation: if mirrored {
+            -local.rotation
+        } else {
+            local.rotation
+        },
+    }
+}
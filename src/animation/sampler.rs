@@ -0,0 +1,79 @@
+use super::state::{State, StateData};
+use common::to_string;
+use libspine_sys::*;
+use raw::*;
+use skeleton::data::Data as SkeletonData;
+use skeleton::Skeleton;
+use std::ptr;
+use std::rc::Rc;
+
+// A private skeleton posed by one animation at a time, starting from the setup pose. Used
+// where an animation has to be evaluated apart from the state driving the skeleton.
+pub(crate) struct Sampler {
+    skeleton: Skeleton,
+    state: State,
+    animation: *const spAnimation,
+}
+
+impl Sampler {
+    pub(crate) fn new(data: Rc<SkeletonData>) -> Result<Self, Error> {
+        let skeleton = Skeleton::from_data(Rc::clone(&data))?;
+        let state = State::from_data(Rc::new(StateData::from_skeleton_data(data)?))?;
+
+        Ok(Sampler {
+            skeleton,
+            state,
+            animation: ptr::null(),
+        })
+    }
+
+    // local bone poses of the animation alone at the given animation time
+    pub(crate) fn pose(&mut self, animation: *const spAnimation, time: f32) -> Option<&Skeleton> {
+        if self.animation != animation {
+            let name = unsafe {
+                animation
+                    .as_ref()
+                    .map(|animation| to_string(animation.name))?
+            };
+            self.animation = ptr::null();
+            self.state.clear_tracks();
+            self.state.set_animation_by_name(0, &name, false).ok()?;
+            self.animation = animation;
+        }
+
+        if let Some(mut entry) = self.state.current(0) {
+            entry.set_track_time(time);
+        }
+        self.skeleton.set_bones_to_setup_pose();
+        self.state.apply(&mut self.skeleton).ok()?;
+        // events fired while sampling are of no interest
+        self.state.drain_events().for_each(drop);
+
+        Some(&self.skeleton)
+    }
+}
+
+pub(crate) fn bone(skeleton: &Skeleton, index: usize) -> Option<&spBone> {
+    let raw = skeleton.as_raw();
+    if raw.bones.is_null() || index >= raw.bonesCount.max(0) as usize {
+        return None;
+    }
+
+    unsafe { (*raw.bones.add(index)).as_ref() }
+}
+
+pub(crate) fn bone_mut(skeleton: &mut Skeleton, index: usize) -> Option<&mut spBone> {
+    let raw = skeleton.as_raw_mut();
+    unsafe {
+        if (*raw).bones.is_null() || index >= (*raw).bonesCount.max(0) as usize {
+            return None;
+        }
+
+        (*(*raw).bones.add(index)).as_mut()
+    }
+}
+
+// into -180..180
+pub(crate) fn wrap_degrees(degrees: f32) -> f32 {
+    degrees - ((degrees + 180.) / 360.).floor() * 360.
+}
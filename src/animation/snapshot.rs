@@ -0,0 +1,270 @@
+use super::events::AnimationEvent;
+use super::state::State;
+use common::{spine_calloc, spine_free};
+use libspine_sys::*;
+use raw::*;
+use std::os::raw::c_void;
+use std::ptr;
+use std::slice;
+
+#[derive(Debug, Clone)]
+struct EntrySnapshot {
+    animation: *mut spAnimation,
+    listener: spAnimationStateListener,
+    loop_: i32,
+    thresholds: [f32; 3],
+    animation_times: [f32; 4],
+    track_times: [f32; 6],
+    mixing: [f32; 5],
+    timelines_rotation: Vec<f32>,
+    renderer_object: *mut c_void,
+    user_data: *mut c_void,
+}
+
+#[derive(Debug, Clone)]
+struct TrackSnapshot {
+    index: i32,
+    // deepest `mixingFrom` entry first, the current entry last
+    mixing: Vec<EntrySnapshot>,
+    queue: Vec<EntrySnapshot>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    data: *mut spAnimationStateData,
+    time_scale: f32,
+    tracks: Vec<TrackSnapshot>,
+    // not drained yet when the snapshot was taken
+    events: Vec<AnimationEvent>,
+}
+
+impl State {
+    pub fn snapshot(&self) -> Snapshot {
+        let raw = self.as_raw();
+        let tracks = self
+            .tracks()
+            .map(|current| {
+                let current = current.as_raw() as *const spTrackEntry;
+                let mut mixing = collect_entries(current, |entry| entry.mixingFrom);
+                mixing.reverse();
+
+                TrackSnapshot {
+                    index: unsafe { (*current).trackIndex },
+                    mixing,
+                    queue: collect_entries(unsafe { (*current).next }, |entry| entry.next),
+                }
+            })
+            .collect();
+
+        Snapshot {
+            data: raw.data,
+            time_scale: raw.timeScale,
+            tracks,
+            events: self.pending_events(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+        if snapshot.data != self.as_raw().data {
+            return Err(Error::SnapshotMismatch);
+        }
+
+        // clearing and recreating entries would report interrupts, ends and starts that never
+        // happened, the queue is set back to the snapshot's instead
+        let listener = self.as_raw().listener;
+        unsafe {
+            mute_entries(self.as_raw_mut());
+            (*self.as_raw_mut()).listener = None;
+        }
+        let result = self.restore_tracks(snapshot);
+        unsafe { (*self.as_raw_mut()).listener = listener };
+        result?;
+
+        self.set_pending_events(&snapshot.events);
+        self.set_time_scale(snapshot.time_scale);
+
+        Ok(())
+    }
+
+    fn restore_tracks(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+        self.clear_tracks();
+
+        for track in snapshot.tracks.iter() {
+            let mut created = Vec::with_capacity(track.mixing.len() + track.queue.len());
+
+            for entry in track.mixing.iter() {
+                let ptr = unsafe {
+                    spAnimationState_setAnimation(
+                        self.as_raw_mut(),
+                        track.index,
+                        entry.animation,
+                        entry.loop_,
+                    )
+                };
+                let ptr = try_wrap!(ptr, "spTrackEntry", |raw: NonNull<_>| raw.as_ptr())?;
+                // spine-c drops an entry that was never applied instead of mixing from it
+                unsafe { (*ptr).nextTrackLast = entry.track_times[3] };
+                created.push(ptr);
+            }
+
+            for entry in track.queue.iter() {
+                let ptr = unsafe {
+                    spAnimationState_addAnimation(
+                        self.as_raw_mut(),
+                        track.index,
+                        entry.animation,
+                        entry.loop_,
+                        entry.track_times[0],
+                    )
+                };
+                created.push(try_wrap!(ptr, "spTrackEntry", |raw: NonNull<_>| raw.as_ptr())?);
+            }
+
+            let entries = track.mixing.iter().chain(track.queue.iter());
+            for (ptr, entry) in created.into_iter().zip(entries) {
+                unsafe { restore_entry(&mut *ptr, entry) };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// silences the listeners of every entry about to be cleared
+unsafe fn mute_entries(state: *mut spAnimationState) {
+    if (*state).tracks.is_null() {
+        return;
+    }
+
+    for index in 0..(*state).tracksCount.max(0) as usize {
+        let current = *(*state).tracks.add(index);
+        mute_chain(current, |entry| entry.mixingFrom);
+        if let Some(current) = current.as_ref() {
+            mute_chain(current.next, |entry| entry.next);
+        }
+    }
+}
+
+unsafe fn mute_chain<F>(first: *mut spTrackEntry, next: F)
+where
+    F: Fn(&spTrackEntry) -> *mut spTrackEntry,
+{
+    let mut current = first;
+    while let Some(entry) = current.as_mut() {
+        entry.listener = None;
+        current = next(entry);
+    }
+}
+
+fn collect_entries<F>(first: *const spTrackEntry, next: F) -> Vec<EntrySnapshot>
+where
+    F: Fn(&spTrackEntry) -> *mut spTrackEntry,
+{
+    let mut entries = Vec::new();
+    let mut current = first;
+
+    while let Some(entry) = unsafe { current.as_ref() } {
+        entries.push(capture_entry(entry));
+        current = next(entry);
+    }
+
+    entries
+}
+
+fn capture_entry(entry: &spTrackEntry) -> EntrySnapshot {
+    let timelines_rotation = if entry.timelinesRotation.is_null() {
+        Vec::new()
+    } else {
+        let count = entry.timelinesRotationCount as usize;
+        unsafe { slice::from_raw_parts(entry.timelinesRotation, count).to_vec() }
+    };
+
+    EntrySnapshot {
+        animation: entry.animation,
+        listener: entry.listener,
+        loop_: entry.loop_,
+        thresholds: [
+            entry.eventThreshold,
+            entry.attachmentThreshold,
+            entry.drawOrderThreshold,
+        ],
+        animation_times: [
+            entry.animationStart,
+            entry.animationEnd,
+            entry.animationLast,
+            entry.nextAnimationLast,
+        ],
+        track_times: [
+            entry.delay,
+            entry.trackTime,
+            entry.trackLast,
+            entry.nextTrackLast,
+            entry.trackEnd,
+            entry.timeScale,
+        ],
+        mixing: [
+            entry.alpha,
+            entry.mixTime,
+            entry.mixDuration,
+            entry.interruptAlpha,
+            entry.totalAlpha,
+        ],
+        timelines_rotation,
+        renderer_object: entry.rendererObject,
+        user_data: entry.userData,
+    }
+}
+
+unsafe fn restore_entry(entry: &mut spTrackEntry, snapshot: &EntrySnapshot) {
+    entry.listener = snapshot.listener;
+    entry.loop_ = snapshot.loop_;
+
+    let [event, attachment, draw_order] = snapshot.thresholds;
+    entry.eventThreshold = event;
+    entry.attachmentThreshold = attachment;
+    entry.drawOrderThreshold = draw_order;
+
+    let [start, end, last, next_last] = snapshot.animation_times;
+    entry.animationStart = start;
+    entry.animationEnd = end;
+    entry.animationLast = last;
+    entry.nextAnimationLast = next_last;
+
+    let [delay, track_time, track_last, next_track_last, track_end, time_scale] =
+        snapshot.track_times;
+    entry.delay = delay;
+    entry.trackTime = track_time;
+    entry.trackLast = track_last;
+    entry.nextTrackLast = next_track_last;
+    entry.trackEnd = track_end;
+    entry.timeScale = time_scale;
+
+    let [alpha, mix_time, mix_duration, interrupt_alpha, total_alpha] = snapshot.mixing;
+    entry.alpha = alpha;
+    entry.mixTime = mix_time;
+    entry.mixDuration = mix_duration;
+    entry.interruptAlpha = interrupt_alpha;
+    entry.totalAlpha = total_alpha;
+
+    // an empty rotation buffer makes spine-c treat the next apply as the first frame
+    let count = snapshot.timelines_rotation.len();
+    if count != entry.timelinesRotationCount as usize || entry.timelinesRotation.is_null() {
+        spine_free(entry.timelinesRotation);
+        entry.timelinesRotation = if count > 0 {
+            spine_calloc(count)
+        } else {
+            ptr::null_mut()
+        };
+    }
+    if count > 0 {
+        ptr::copy_nonoverlapping(
+            snapshot.timelines_rotation.as_ptr(),
+            entry.timelinesRotation,
+            count,
+        );
+    }
+    entry.timelinesRotationCount = count as i32;
+
+    entry.rendererObject = snapshot.renderer_object;
+    entry.userData = snapshot.user_data;
+}
@@ -1,15 +1,23 @@
+use animation::blend::Layering;
+use animation::events::{self, AnimationEvent, EventQueue};
+use animation::track_entry::TrackEntry;
 use animation::Animation;
+use common::from_raw_buf;
+use extension::resume_panic;
 use libspine_sys::*;
 use raw::*;
 use skeleton::{data::Data as SkeletonData, Skeleton};
 use std::ffi::CString;
-use std::io::{Error, ErrorKind};
-use std::ptr::NonNull;
+use std::ptr::{self, NonNull};
 use std::rc::Rc;
+use std::vec::Drain;
 
 pub struct State {
     data: Rc<StateData>,
     raw: NonNull<spAnimationState>,
+    // boxed so the listener keeps a stable address when the state moves
+    events: Box<EventQueue>,
+    layering: Layering,
 }
 
 impl_as_raw!(State, raw, spAnimationState);
@@ -21,29 +29,194 @@ impl State {
             spAnimationState_create(data.as_raw() as *const _ as *mut spAnimationStateData)
         };
 
-        try_wrap!(ptr, |raw| State{ data, raw })
-    }
+        let mut state = try_wrap!(ptr, "spAnimationState", |raw| State {
+            data,
+            raw,
+            events: Box::new(EventQueue::default()),
+            layering: Layering::default(),
+        })?;
 
-    pub fn set_animation(&mut self, track_index: i32, animation: &Animation, loop_: bool) {
         unsafe {
-            let c_str = CString::new(animation.name.clone()).unwrap();
-            let _track = spAnimationState_setAnimationByName(
+            let raw = state.raw.as_mut();
+            raw.rendererObject = &mut *state.events as *mut EventQueue as *mut _;
+            raw.listener = Some(events::listener);
+        }
+
+        Ok(state)
+    }
+
+    pub fn set_animation(
+        &mut self,
+        track_index: i32,
+        animation: &Animation,
+        loop_: bool,
+    ) -> Result<TrackEntry<'_>, Error> {
+        self.set_animation_by_name(track_index, &animation.name, loop_)
+    }
+
+    pub fn set_animation_by_name(
+        &mut self,
+        track_index: i32,
+        name: &str,
+        loop_: bool,
+    ) -> Result<TrackEntry<'_>, Error> {
+        let animation = self.find_animation(track_index, name)?;
+        let ptr = unsafe {
+            spAnimationState_setAnimation(self.as_raw_mut(), track_index, animation, loop_ as i32)
+        };
+        resume_panic();
+
+        try_wrap!(ptr, "spTrackEntry", TrackEntry::from_raw)
+    }
+
+    pub fn add_animation(
+        &mut self,
+        track_index: i32,
+        animation: &Animation,
+        loop_: bool,
+        delay: f32,
+    ) -> Result<TrackEntry<'_>, Error> {
+        self.add_animation_by_name(track_index, &animation.name, loop_, delay)
+    }
+
+    pub fn add_animation_by_name(
+        &mut self,
+        track_index: i32,
+        name: &str,
+        loop_: bool,
+        delay: f32,
+    ) -> Result<TrackEntry<'_>, Error> {
+        let animation = self.find_animation(track_index, name)?;
+        let ptr = unsafe {
+            spAnimationState_addAnimation(
                 self.as_raw_mut(),
                 track_index,
-                c_str.as_ptr(),
+                animation,
                 loop_ as i32,
-            );
+                delay,
+            )
+        };
+        resume_panic();
+
+        try_wrap!(ptr, "spTrackEntry", TrackEntry::from_raw)
+    }
+
+    pub fn tracks(&self) -> impl Iterator<Item = TrackEntry<'_>> {
+        let raw = self.as_raw();
+        let tracks_raw = if raw.tracks.is_null() {
+            Vec::new()
+        } else {
+            unsafe { from_raw_buf(raw.tracks, raw.tracksCount as usize) }
+        };
+
+        tracks_raw
+            .into_iter()
+            .filter_map(|p| NonNull::new(p).map(TrackEntry::from_raw))
+    }
+
+    pub fn current(&self, track_index: i32) -> Option<TrackEntry<'_>> {
+        let raw = self.as_raw();
+        if track_index < 0 || track_index >= raw.tracksCount {
+            return None;
+        }
+
+        let ptr = unsafe { *raw.tracks.offset(track_index as isize) };
+
+        NonNull::new(ptr).map(TrackEntry::from_raw)
+    }
+
+    pub fn is_complete(&self, track_index: i32) -> bool {
+        match self.current(track_index) {
+            Some(entry) => entry.is_complete(),
+            None => true,
         }
     }
 
+    pub fn time_scale(&self) -> f32 {
+        self.as_raw().timeScale
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        unsafe {
+            self.raw.as_mut().timeScale = time_scale;
+        }
+    }
+
+    pub fn clear_track(&mut self, track_index: i32) -> Result<(), Error> {
+        if track_index < 0 {
+            return Err(Error::InvalidTrackIndex(track_index));
+        }
+
+        unsafe {
+            spAnimationState_clearTrack(self.as_raw_mut(), track_index);
+        }
+        resume_panic();
+
+        Ok(())
+    }
+
+    pub fn clear_tracks(&mut self) {
+        unsafe {
+            spAnimationState_clearTracks(self.as_raw_mut());
+        }
+        resume_panic();
+    }
+
     pub fn update(&mut self, delta: f32) {
         unsafe {
             spAnimationState_update(self.as_raw_mut(), delta);
         }
+        resume_panic();
     }
 
-    pub fn apply(&mut self, skeleton: &mut Skeleton) {
+    // timelines index bones and slots, so the skeleton has to be built from the state's data
+    pub fn apply(&mut self, skeleton: &mut Skeleton) -> Result<(), Error> {
+        if !ptr::eq(skeleton.as_raw().data, self.data.data.as_raw()) {
+            return Err(Error::DataMismatch);
+        }
+
+        self.layering.begin(unsafe { self.raw.as_ref() });
         let _result = unsafe { spAnimationState_apply(self.as_raw_mut(), skeleton.as_raw_mut()) };
+        self.layering.end(&self.data.data, skeleton);
+        resume_panic();
+
+        Ok(())
+    }
+
+    // notifications raised since the last call, by `update`, `apply` and track changes
+    pub fn drain_events(&mut self) -> Drain<'_, AnimationEvent> {
+        self.events.drain()
+    }
+
+    pub(crate) fn pending_events(&self) -> Vec<AnimationEvent> {
+        self.events.pending().to_vec()
+    }
+
+    pub(crate) fn set_pending_events(&mut self, events: &[AnimationEvent]) {
+        self.events.set_pending(events);
+    }
+
+    // pending notifications move over, the ones already queued here are dropped
+    pub(crate) fn take_events(&mut self, from: &mut State) {
+        self.events.replace_with(&mut from.events);
+    }
+
+    // spine-c dereferences the animation without checking it, so it is resolved here first
+    fn find_animation(&self, track_index: i32, name: &str) -> Result<*mut spAnimation, Error> {
+        if track_index < 0 {
+            return Err(Error::InvalidTrackIndex(track_index));
+        }
+
+        let c_name = CString::new(name)?;
+        let ptr = unsafe {
+            spSkeletonData_findAnimation((*self.as_raw().data).skeletonData, c_name.as_ptr())
+        };
+
+        if ptr.is_null() {
+            return Err(Error::AnimationNotFound(name.to_owned()));
+        }
+
+        Ok(ptr)
     }
 }
 
@@ -69,7 +242,7 @@ impl StateData {
             spAnimationStateData_create(data.as_raw() as *const _ as *mut spSkeletonData)
         };
 
-        try_wrap!(ptr, |raw| StateData { data, raw })
+        try_wrap!(ptr, "spAnimationStateData", |raw| StateData { data, raw })
     }
 
     pub fn set_default_mix(&mut self, val: f32) {
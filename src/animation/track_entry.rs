@@ -0,0 +1,157 @@
+use animation::blend::{self, MixBlend};
+use animation::Animation;
+use animation::state::State;
+use libspine_sys::*;
+use raw::*;
+use std::marker::PhantomData;
+
+// spine-c frees entries once they end, are replaced or the state is disposed, which only
+// happens through `&mut State`. Handles borrow the state so none of them can outlive it.
+pub struct TrackEntry<'a> {
+    raw: NonNull<spTrackEntry>,
+    state: PhantomData<&'a State>,
+}
+
+unsafe impl<'a> AsRaw for TrackEntry<'a> {
+    type Raw = spTrackEntry;
+    fn as_raw(&self) -> &Self::Raw {
+        unsafe { self.raw.as_ref() }
+    }
+}
+
+unsafe impl<'a> AsRawMut for TrackEntry<'a> {
+    fn as_raw_mut(&mut self) -> *mut <Self as AsRaw>::Raw {
+        unsafe { self.raw.as_mut() }
+    }
+}
+
+impl<'a> TrackEntry<'a> {
+    pub fn from_raw(raw: NonNull<spTrackEntry>) -> Self {
+        TrackEntry {
+            raw,
+            state: PhantomData,
+        }
+    }
+
+    pub fn track_index(&self) -> i32 {
+        self.as_raw().trackIndex
+    }
+
+    pub fn animation(&self) -> Option<Animation> {
+        unsafe { self.as_raw().animation.as_ref().map(Animation::from) }
+    }
+
+    pub fn is_loop(&self) -> bool {
+        self.as_raw().loop_ != 0
+    }
+
+    pub fn set_loop(&mut self, loop_: bool) {
+        unsafe {
+            self.raw.as_mut().loop_ = loop_ as i32;
+        }
+    }
+
+    pub fn delay(&self) -> f32 {
+        self.as_raw().delay
+    }
+
+    pub fn set_delay(&mut self, delay: f32) {
+        unsafe {
+            self.raw.as_mut().delay = delay;
+        }
+    }
+
+    pub fn track_time(&self) -> f32 {
+        self.as_raw().trackTime
+    }
+
+    pub fn set_track_time(&mut self, track_time: f32) {
+        unsafe {
+            self.raw.as_mut().trackTime = track_time;
+        }
+    }
+
+    pub fn track_end(&self) -> f32 {
+        self.as_raw().trackEnd
+    }
+
+    pub fn set_track_end(&mut self, track_end: f32) {
+        unsafe {
+            self.raw.as_mut().trackEnd = track_end;
+        }
+    }
+
+    pub fn animation_start(&self) -> f32 {
+        self.as_raw().animationStart
+    }
+
+    pub fn animation_end(&self) -> f32 {
+        self.as_raw().animationEnd
+    }
+
+    pub fn animation_last(&self) -> f32 {
+        self.as_raw().animationLast
+    }
+
+    pub fn is_complete(&self) -> bool {
+        let raw = self.as_raw();
+
+        raw.trackTime >= raw.animationEnd - raw.animationStart
+    }
+
+    pub fn next(&self) -> Option<TrackEntry<'a>> {
+        NonNull::new(self.as_raw().next).map(TrackEntry::from_raw)
+    }
+
+    pub fn mixing_from(&self) -> Option<TrackEntry<'a>> {
+        NonNull::new(self.as_raw().mixingFrom).map(TrackEntry::from_raw)
+    }
+
+    pub fn animation_time(&self) -> f32 {
+        unsafe { spTrackEntry_getAnimationTime(self.raw.as_ptr()) }
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.as_raw().timeScale
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        unsafe {
+            self.raw.as_mut().timeScale = time_scale;
+        }
+    }
+
+    pub fn mix_blend(&self) -> MixBlend {
+        blend::from_user_data(self.as_raw().userData)
+    }
+
+    pub fn set_mix_blend(&mut self, mix_blend: MixBlend) {
+        unsafe {
+            self.raw.as_mut().userData = blend::to_user_data(mix_blend);
+        }
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.as_raw().alpha
+    }
+
+    pub fn set_alpha(&mut self, alpha: f32) {
+        unsafe {
+            self.raw.as_mut().alpha = alpha;
+        }
+    }
+
+    pub fn mix_time(&self) -> f32 {
+        self.as_raw().mixTime
+    }
+
+    pub fn mix_duration(&self) -> f32 {
+        self.as_raw().mixDuration
+    }
+
+    pub fn set_mix_duration(&mut self, mix_duration: f32) {
+        unsafe {
+            self.raw.as_mut().mixDuration = mix_duration;
+        }
+    }
+}
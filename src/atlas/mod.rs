@@ -1,4 +1,6 @@
 use self::page::Page;
+use self::region::Region;
+use extension::resume_panic;
 use libspine_sys::*;
 use raw::*;
 use std::ffi::CString;
@@ -21,17 +23,62 @@ impl Atlas {
         let c_path = CString::new(path)?;
         let ptr = unsafe { spAtlas_createFromFile(c_path.as_ptr(), ptr::null_mut()) };
 
-        let raw = try_wrap!(ptr, |raw| raw)?;
-        let pages_ptr = unsafe { raw.as_ref().pages };
-        let pages = try_wrap!(pages_ptr, |pages_raw| Atlas::collect_pages(pages_raw))?;
+        // wrap before validating so that early returns and panics still dispose the atlas
+        let atlas = NonNull::new(ptr).map(|raw| {
+            let pages = NonNull::new(unsafe { raw.as_ref().pages })
+                .map(Atlas::collect_pages)
+                .unwrap_or_default();
+            Atlas { raw, pages }
+        });
+        resume_panic();
+        let atlas = atlas.ok_or_else(|| Error::Parse {
+            file: path.to_owned(),
+            message: "unable to read atlas".to_owned(),
+        })?;
 
-        Ok(Atlas { raw, pages })
+        if atlas.pages.is_empty() {
+            return Err(Error::NullPointer { what: "spAtlas.pages" });
+        }
+
+        Ok(atlas)
+    }
+
+    // pages whose texture hook failed keep no renderer object, which only matters for drawing
+    pub fn check_textures(&self) -> Result<(), Error> {
+        match self.pages().find(|page| page.renderer_object().is_none()) {
+            Some(page) => Err(Error::TextureLoad {
+                path: page.name.clone(),
+                message: "texture was not created for atlas page".to_owned(),
+            }),
+            None => Ok(()),
+        }
     }
 
-    pub fn pages(&self) -> Iter<Page> {
+    pub fn pages(&self) -> Iter<'_, Page> {
         self.pages.iter()
     }
 
+    pub fn regions(&self) -> Vec<Region<'_>> {
+        let mut container = Vec::new();
+        let mut current_region = NonNull::new(self.as_raw().regions).map(Region::from_raw);
+
+        while let Some(region) = current_region {
+            current_region = region.next();
+            container.push(region);
+        }
+
+        container
+    }
+
+    pub fn find_region(&self, name: &str) -> Option<Region<'_>> {
+        let c_name = CString::new(name).ok()?;
+        let ptr = unsafe {
+            spAtlas_findRegion(self.as_raw() as *const _ as *mut spAtlas, c_name.as_ptr())
+        };
+
+        NonNull::new(ptr).map(Region::from_raw)
+    }
+
     fn collect_pages(first: NonNull<spAtlasPage>) -> Vec<Page> {
         let mut container = Vec::new();
         let mut current_page = Some(Page::from_raw(first));
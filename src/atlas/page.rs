@@ -1,12 +1,119 @@
 use extension::Texture;
+use libspine_sys::spAtlasFilter::*;
+use libspine_sys::spAtlasFormat::*;
+use libspine_sys::spAtlasWrap::*;
 use libspine_sys::*;
 use std::ffi::CStr;
 use raw::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    Unknown,
+    Alpha,
+    Intensity,
+    LuminanceAlpha,
+    Rgb565,
+    Rgba4444,
+    Rgb888,
+    #[default]
+    Rgba8888,
+}
+
+impl From<spAtlasFormat> for Format {
+    fn from(raw: spAtlasFormat) -> Self {
+        match raw {
+            SP_ATLAS_UNKNOWN_FORMAT => Format::Unknown,
+            SP_ATLAS_ALPHA => Format::Alpha,
+            SP_ATLAS_INTENSITY => Format::Intensity,
+            SP_ATLAS_LUMINANCE_ALPHA => Format::LuminanceAlpha,
+            SP_ATLAS_RGB565 => Format::Rgb565,
+            SP_ATLAS_RGBA4444 => Format::Rgba4444,
+            SP_ATLAS_RGB888 => Format::Rgb888,
+            SP_ATLAS_RGBA8888 => Format::Rgba8888,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    Unknown,
+    Nearest,
+    Linear,
+    MipMap,
+    MipMapNearestNearest,
+    MipMapLinearNearest,
+    MipMapNearestLinear,
+    MipMapLinearLinear,
+}
+
+impl From<spAtlasFilter> for Filter {
+    fn from(raw: spAtlasFilter) -> Self {
+        match raw {
+            SP_ATLAS_UNKNOWN_FILTER => Filter::Unknown,
+            SP_ATLAS_NEAREST => Filter::Nearest,
+            SP_ATLAS_LINEAR => Filter::Linear,
+            SP_ATLAS_MIPMAP => Filter::MipMap,
+            SP_ATLAS_MIPMAP_NEAREST_NEAREST => Filter::MipMapNearestNearest,
+            SP_ATLAS_MIPMAP_LINEAR_NEAREST => Filter::MipMapLinearNearest,
+            SP_ATLAS_MIPMAP_NEAREST_LINEAR => Filter::MipMapNearestLinear,
+            SP_ATLAS_MIPMAP_LINEAR_LINEAR => Filter::MipMapLinearLinear,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    MirroredRepeat,
+    ClampToEdge,
+    Repeat,
+}
+
+impl From<spAtlasWrap> for Wrap {
+    fn from(raw: spAtlasWrap) -> Self {
+        match raw {
+            SP_ATLAS_MIRROREDREPEAT => Wrap::MirroredRepeat,
+            SP_ATLAS_CLAMPTOEDGE => Wrap::ClampToEdge,
+            SP_ATLAS_REPEAT => Wrap::Repeat,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sampler {
+    pub min_filter: Filter,
+    pub mag_filter: Filter,
+    pub u_wrap: Wrap,
+    pub v_wrap: Wrap,
+}
+
+impl Sampler {
+    pub fn from_raw(raw: &spAtlasPage) -> Self {
+        Sampler {
+            min_filter: Filter::from(raw.minFilter),
+            mag_filter: Filter::from(raw.magFilter),
+            u_wrap: Wrap::from(raw.uWrap),
+            v_wrap: Wrap::from(raw.vWrap),
+        }
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Sampler {
+            min_filter: Filter::Linear,
+            mag_filter: Filter::Linear,
+            u_wrap: Wrap::ClampToEdge,
+            v_wrap: Wrap::ClampToEdge,
+        }
+    }
+}
+
 pub struct Page {
     pub name: String,
     pub width: i32,
     pub height: i32,
+    pub format: Format,
+    pub sampler: Sampler,
     raw: NonNull<spAtlasPage>,
 }
 
@@ -23,6 +130,8 @@ impl Page {
             name,
             width,
             height,
+            format: Format::from(raw_ref.format),
+            sampler: Sampler::from_raw(raw_ref),
             raw,
         }
     }
@@ -34,6 +143,6 @@ impl Page {
     }
 
     pub fn next(&self) -> Option<Page> {
-        NonNull::new(self.as_raw().next).map(|raw| Page::from_raw(raw))
+        NonNull::new(self.as_raw().next).map(Page::from_raw)
     }
 }
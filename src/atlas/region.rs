@@ -1,22 +1,116 @@
 use super::page::Page;
+use super::Atlas;
+use common::to_string;
 use libspine_sys::*;
 use raw::*;
+use std::marker::PhantomData;
 
-pub struct Region {
+// regions are freed with their atlas, handles borrow it so none of them can outlive it
+pub struct Region<'a> {
     raw: NonNull<spAtlasRegion>,
+    atlas: PhantomData<&'a Atlas>,
 }
 
-impl_as_raw!(Region, raw, spAtlasRegion);
-impl_as_raw_mut!(Region, raw);
+unsafe impl<'a> AsRaw for Region<'a> {
+    type Raw = spAtlasRegion;
+    fn as_raw(&self) -> &Self::Raw {
+        unsafe { self.raw.as_ref() }
+    }
+}
+
+unsafe impl<'a> AsRawMut for Region<'a> {
+    fn as_raw_mut(&mut self) -> *mut <Self as AsRaw>::Raw {
+        unsafe { self.raw.as_mut() }
+    }
+}
 
-impl Region {
+impl<'a> Region<'a> {
     pub fn from_raw(raw: NonNull<spAtlasRegion>) -> Self {
         Region {
-            raw
+            raw,
+            atlas: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        unsafe { to_string(self.as_raw().name) }
+    }
+
+    pub fn index(&self) -> i32 {
+        self.as_raw().index
+    }
+
+    pub fn x(&self) -> i32 {
+        self.as_raw().x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.as_raw().y
+    }
+
+    pub fn width(&self) -> i32 {
+        self.as_raw().width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.as_raw().height
+    }
+
+    pub fn uvs(&self) -> (f32, f32, f32, f32) {
+        let raw = self.as_raw();
+
+        (raw.u, raw.v, raw.u2, raw.v2)
+    }
+
+    pub fn rotate(&self) -> bool {
+        self.as_raw().rotate != 0
+    }
+
+    // spine-c 3.6 atlases only know 90 degree rotation
+    pub fn degrees(&self) -> i32 {
+        if self.rotate() {
+            90
+        } else {
+            0
         }
     }
 
+    pub fn offset(&self) -> (i32, i32) {
+        let raw = self.as_raw();
+
+        (raw.offsetX, raw.offsetY)
+    }
+
+    pub fn original_size(&self) -> (i32, i32) {
+        let raw = self.as_raw();
+
+        (raw.originalWidth, raw.originalHeight)
+    }
+
+    pub fn splits(&self) -> Option<[i32; 4]> {
+        unsafe { read_quad(self.as_raw().splits) }
+    }
+
+    pub fn pads(&self) -> Option<[i32; 4]> {
+        unsafe { read_quad(self.as_raw().pads) }
+    }
+
     pub fn page(&self) -> Option<Page> {
-        NonNull::new(self.as_raw().page).map(|raw| Page::from_raw(raw))
+        NonNull::new(self.as_raw().page).map(Page::from_raw)
+    }
+
+    pub fn next(&self) -> Option<Region<'a>> {
+        NonNull::new(self.as_raw().next).map(Region::from_raw)
     }
 }
+
+unsafe fn read_quad(ptr: *const i32) -> Option<[i32; 4]> {
+    if ptr.is_null() {
+        return None;
+    }
+
+    let mut quad = [0; 4];
+    ptr.copy_to(quad.as_mut_ptr(), 4);
+
+    Some(quad)
+}
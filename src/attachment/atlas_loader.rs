@@ -0,0 +1,204 @@
+use super::loader::AttachmentLoader;
+use super::mesh::Mesh;
+use super::region::Region;
+use atlas::page::{Filter, Sampler, Wrap};
+use atlas::region::Region as AtlasRegion;
+use atlas::Atlas;
+use common::spine_calloc;
+use extension::Texture;
+use libspine_sys::*;
+use raw::*;
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+use std::rc::Rc;
+
+const PLACEHOLDER_NAME: &str = "spiner-placeholder";
+const PLACEHOLDER_SIZE: u32 = 16;
+
+#[derive(Debug, Clone)]
+pub struct MissingRegion {
+    pub skin: String,
+    pub attachment: String,
+    pub path: String,
+}
+
+// Resolves region and mesh attachments from an atlas like spine-c's own atlas loader.
+// In lenient mode a missing region is replaced by a magenta checkerboard and recorded,
+// instead of failing the whole skeleton.
+pub struct AtlasLoader {
+    // resolved regions are only borrowed, the loader keeps their atlas alive
+    atlas: Rc<Atlas>,
+    placeholder: Option<Placeholder>,
+    missing: Rc<RefCell<Vec<MissingRegion>>>,
+}
+
+impl AtlasLoader {
+    pub fn new(atlas: Rc<Atlas>) -> Self {
+        AtlasLoader {
+            atlas,
+            placeholder: None,
+            missing: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    pub fn lenient(atlas: Rc<Atlas>) -> Result<Self, Error> {
+        let mut loader = AtlasLoader::new(atlas);
+        loader.placeholder = Some(Placeholder::new()?);
+
+        Ok(loader)
+    }
+
+    // shared with the loader, so the regions stay readable once it moved into the skeleton data
+    pub fn missing_regions(&self) -> Rc<RefCell<Vec<MissingRegion>>> {
+        Rc::clone(&self.missing)
+    }
+
+    fn resolve(&mut self, skin: &str, name: &str, path: &str) -> Result<AtlasRegion<'_>, Error> {
+        let c_path = CString::new(path)?;
+        let ptr = unsafe { spAtlas_findRegion(
+                self.atlas.as_raw() as *const _ as *mut spAtlas,
+                c_path.as_ptr(),
+            ) };
+        if let Some(raw) = NonNull::new(ptr) {
+            return Ok(AtlasRegion::from_raw(raw));
+        }
+
+        match self.placeholder {
+            Some(ref placeholder) => {
+                self.missing.borrow_mut().push(MissingRegion {
+                    skin: skin.to_owned(),
+                    attachment: name.to_owned(),
+                    path: path.to_owned(),
+                });
+
+                Ok(AtlasRegion::from_raw(placeholder.region))
+            }
+            None => Err(Error::RegionNotFound(path.to_owned())),
+        }
+    }
+}
+
+impl AttachmentLoader for AtlasLoader {
+    fn create_region(
+        &mut self,
+        skin: &str,
+        name: &str,
+        path: &str,
+        attachment: &mut Region,
+    ) -> Result<bool, Error> {
+        let region = self.resolve(skin, name, path)?;
+        attachment.set_atlas_region(&region);
+
+        Ok(true)
+    }
+
+    fn create_mesh(
+        &mut self,
+        skin: &str,
+        name: &str,
+        path: &str,
+        attachment: &mut Mesh,
+    ) -> Result<bool, Error> {
+        let region = self.resolve(skin, name, path)?;
+        attachment.set_atlas_region(&region);
+
+        Ok(true)
+    }
+}
+
+// a standalone page and region, not linked into any atlas
+struct Placeholder {
+    page: NonNull<spAtlasPage>,
+    region: NonNull<spAtlasRegion>,
+}
+
+impl Placeholder {
+    fn new() -> Result<Self, Error> {
+        let c_name = CString::new(PLACEHOLDER_NAME)?;
+        let page_ptr = unsafe { spAtlasPage_create(ptr::null_mut(), c_name.as_ptr()) };
+        let mut page = try_wrap!(page_ptr, "spAtlasPage", |raw| raw)?;
+        let region_ptr = unsafe { spAtlasRegion_create() };
+        let mut region = match NonNull::new(region_ptr) {
+            Some(region) => region,
+            None => {
+                unsafe { spAtlasPage_dispose(page.as_ptr()) };
+                return Err(Error::NullPointer { what: "spAtlasRegion" });
+            }
+        };
+
+        let size = PLACEHOLDER_SIZE as i32;
+        // disposed with the page through `_spAtlasPage_disposeTexture`
+        let texture = Box::new(Texture {
+            buffer: checkerboard(PLACEHOLDER_SIZE),
+            width: PLACEHOLDER_SIZE,
+            height: PLACEHOLDER_SIZE,
+            sampler: Sampler {
+                min_filter: Filter::Nearest,
+                mag_filter: Filter::Nearest,
+                u_wrap: Wrap::ClampToEdge,
+                v_wrap: Wrap::ClampToEdge,
+            },
+            ..Default::default()
+        });
+
+        unsafe {
+            let page = page.as_mut();
+            page.width = size;
+            page.height = size;
+            page.rendererObject = Box::into_raw(texture) as *mut _;
+
+            let region = region.as_mut();
+            region.name = spine_str(PLACEHOLDER_NAME);
+            region.page = page;
+            region.u = 0.;
+            region.v = 0.;
+            region.u2 = 1.;
+            region.v2 = 1.;
+            region.width = size;
+            region.height = size;
+            region.originalWidth = size;
+            region.originalHeight = size;
+            region.index = -1;
+        }
+
+        Ok(Placeholder { page, region })
+    }
+}
+
+impl Drop for Placeholder {
+    fn drop(&mut self) {
+        unsafe {
+            spAtlasRegion_dispose(self.region.as_ptr());
+            spAtlasPage_dispose(self.page.as_ptr());
+        }
+    }
+}
+
+fn checkerboard(size: u32) -> Vec<u8> {
+    let cell = (size / 4).max(1);
+    let mut buffer = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let pixel = if (x / cell + y / cell) & 1 == 0 {
+                [255, 0, 255, 255]
+            } else {
+                [0, 0, 0, 255]
+            };
+            buffer.extend_from_slice(&pixel);
+        }
+    }
+
+    buffer
+}
+
+// copied into the spine-c allocator, `spAtlasRegion_dispose` frees the name
+unsafe fn spine_str(value: &str) -> *const c_char {
+    let ptr = spine_calloc::<c_char>(value.len() + 1);
+    if !ptr.is_null() {
+        ptr::copy_nonoverlapping(value.as_ptr() as *const c_char, ptr, value.len());
+    }
+
+    ptr
+}
@@ -1,5 +1,7 @@
-use libspine_sys::spBoundingBoxAttachment;
+use super::vertex::Vertex;
+use libspine_sys::*;
 use raw::*;
+use slot::Slot;
 
 pub struct BoundingBox {
     raw: NonNull<spBoundingBoxAttachment>,
@@ -15,3 +17,4 @@ impl BoundingBox {
 
 impl_as_raw!(BoundingBox, raw, spBoundingBoxAttachment);
 impl_as_raw_mut!(BoundingBox, raw);
+impl_vertex!(BoundingBox);
@@ -1,5 +1,8 @@
-use libspine_sys::spClippingAttachment;
+use super::vertex::Vertex;
+use common::to_string;
+use libspine_sys::*;
 use raw::*;
+use slot::Slot;
 
 pub struct Clipping {
     raw: NonNull<spClippingAttachment>,
@@ -11,7 +14,18 @@ impl Clipping {
             raw
         }
     }
+
+    // clipping stops after this slot in draw order, `None` clips to the end
+    pub fn end_slot(&self) -> Option<String> {
+        unsafe {
+            self.as_raw()
+                .endSlot
+                .as_ref()
+                .map(|slot_data| to_string(slot_data.name))
+        }
+    }
 }
 
 impl_as_raw!(Clipping, raw, spClippingAttachment);
 impl_as_raw_mut!(Clipping, raw);
+impl_vertex!(Clipping);
@@ -0,0 +1,266 @@
+use super::bounding_box::BoundingBox;
+use super::clipping::Clipping;
+use super::mesh::Mesh;
+use super::path::Path;
+use super::point::Point;
+use super::region::Region;
+use super::Attachment;
+use common::{spine_calloc, to_string};
+use extension::{catch_panic, resume_panic};
+use libspine_sys::spAttachmentType::*;
+use libspine_sys::*;
+use raw::*;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+
+// Every `create_*` hook receives an attachment freshly allocated by spine-c and fills it in,
+// returning `Ok(false)` skips the attachment and an error aborts the skeleton loading.
+// Region and mesh hooks usually point the attachment at an atlas region, the built-in
+// `render::Batcher` expects `rendererObject` to be an `spAtlasRegion`.
+pub trait AttachmentLoader {
+    fn create_region(
+        &mut self,
+        skin: &str,
+        name: &str,
+        path: &str,
+        attachment: &mut Region,
+    ) -> Result<bool, Error>;
+
+    fn create_mesh(
+        &mut self,
+        skin: &str,
+        name: &str,
+        path: &str,
+        attachment: &mut Mesh,
+    ) -> Result<bool, Error>;
+
+    fn create_bounding_box(
+        &mut self,
+        _skin: &str,
+        _name: &str,
+        _attachment: &mut BoundingBox,
+    ) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    fn create_path(
+        &mut self,
+        _skin: &str,
+        _name: &str,
+        _attachment: &mut Path,
+    ) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    fn create_point(
+        &mut self,
+        _skin: &str,
+        _name: &str,
+        _attachment: &mut Point,
+    ) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    fn create_clipping(
+        &mut self,
+        _skin: &str,
+        _name: &str,
+        _attachment: &mut Clipping,
+    ) -> Result<bool, Error> {
+        Ok(true)
+    }
+
+    // called once the attachment is fully read
+    fn configure(&mut self, _attachment: &Attachment) {}
+
+    // called right before spine-c frees the attachment
+    fn dispose(&mut self, _attachment: &Attachment) {}
+}
+
+extern "C" {
+    // spine-c attachment loader internals (extension.h)
+    fn _spAttachmentLoader_init(
+        self_: *mut spAttachmentLoader,
+        dispose: unsafe extern "C" fn(*mut spAttachmentLoader),
+        create_attachment: unsafe extern "C" fn(
+            *mut spAttachmentLoader,
+            *mut spSkin,
+            spAttachmentType,
+            *const c_char,
+            *const c_char,
+        ) -> *mut spAttachment,
+        configure_attachment: unsafe extern "C" fn(*mut spAttachmentLoader, *mut spAttachment),
+        dispose_attachment: unsafe extern "C" fn(*mut spAttachmentLoader, *mut spAttachment),
+    );
+    fn _spAttachmentLoader_deinit(self_: *mut spAttachmentLoader);
+    fn _spAttachmentLoader_setError(
+        self_: *mut spAttachmentLoader,
+        error1: *const c_char,
+        error2: *const c_char,
+    );
+}
+
+// `super_` comes first so that spine-c can use the bridge as a plain `spAttachmentLoader`
+#[repr(C)]
+struct Bridge {
+    super_: spAttachmentLoader,
+    loader: *mut Box<dyn AttachmentLoader>,
+}
+
+// Owns an `spAttachmentLoader` forwarding to a Rust `AttachmentLoader`. Attachments call
+// back into their loader when disposed, so it has to outlive the skeleton data it loaded.
+pub struct Loader {
+    raw: NonNull<spAttachmentLoader>,
+}
+
+impl_as_raw!(Loader, raw, spAttachmentLoader);
+impl_as_raw_mut!(Loader, raw);
+
+impl Loader {
+    pub fn new<L: AttachmentLoader + 'static>(loader: L) -> Result<Loader, Error> {
+        // allocated by spine-c, `spAttachmentLoader_dispose` frees it
+        let bridge = unsafe { spine_calloc::<Bridge>(1) };
+        let raw = try_wrap!(bridge, "spAttachmentLoader", |raw: NonNull<Bridge>| raw)?;
+
+        unsafe {
+            let loader: Box<dyn AttachmentLoader> = Box::new(loader);
+            (*bridge).loader = Box::into_raw(Box::new(loader));
+            _spAttachmentLoader_init(
+                bridge as *mut spAttachmentLoader,
+                dispose,
+                create_attachment,
+                configure_attachment,
+                dispose_attachment,
+            );
+        }
+
+        Ok(Loader { raw: raw.cast() })
+    }
+}
+
+impl Drop for Loader {
+    fn drop(&mut self) {
+        unsafe {
+            spAttachmentLoader_dispose(self.raw.as_ptr());
+        }
+        resume_panic();
+    }
+}
+
+unsafe fn loader<'a>(raw: *mut spAttachmentLoader) -> &'a mut dyn AttachmentLoader {
+    &mut **(*(raw as *mut Bridge)).loader
+}
+
+unsafe fn set_error(raw: *mut spAttachmentLoader, message: &str) {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    _spAttachmentLoader_setError(raw, message.as_ptr(), b"\0".as_ptr() as *const c_char);
+}
+
+unsafe extern "C" fn dispose(raw: *mut spAttachmentLoader) {
+    let bridge = raw as *mut Bridge;
+    _spAttachmentLoader_deinit(raw);
+
+    if !(*bridge).loader.is_null() {
+        let loader = Box::from_raw((*bridge).loader);
+        (*bridge).loader = ptr::null_mut();
+        catch_panic(|| drop(loader));
+    }
+}
+
+unsafe extern "C" fn create_attachment(
+    raw: *mut spAttachmentLoader,
+    skin: *mut spSkin,
+    type_: spAttachmentType,
+    name: *const c_char,
+    path: *const c_char,
+) -> *mut spAttachment {
+    let loader = loader(raw);
+    let skin_name = skin
+        .as_ref()
+        .map(|skin| to_string(skin.name))
+        .unwrap_or_default();
+    let name_str = to_string(name);
+    let path_str = to_string(path);
+
+    let (attachment, result): (*mut spAttachment, Option<Result<bool, Error>>) = match type_ {
+        SP_ATTACHMENT_REGION => {
+            let ptr = spRegionAttachment_create(name);
+            let mut region = Region::from_raw(NonNull::new_unchecked(ptr));
+            let result =
+                catch_panic(|| loader.create_region(&skin_name, &name_str, &path_str, &mut region));
+            (ptr as *mut spAttachment, result)
+        }
+        SP_ATTACHMENT_MESH | SP_ATTACHMENT_LINKED_MESH => {
+            let ptr = spMeshAttachment_create(name);
+            let mut mesh = Mesh::from_raw(NonNull::new_unchecked(ptr));
+            let result =
+                catch_panic(|| loader.create_mesh(&skin_name, &name_str, &path_str, &mut mesh));
+            (ptr as *mut spAttachment, result)
+        }
+        SP_ATTACHMENT_BOUNDING_BOX => {
+            let ptr = spBoundingBoxAttachment_create(name);
+            let mut bounding_box = BoundingBox::from_raw(NonNull::new_unchecked(ptr));
+            let result = catch_panic(|| {
+                loader.create_bounding_box(&skin_name, &name_str, &mut bounding_box)
+            });
+            (ptr as *mut spAttachment, result)
+        }
+        SP_ATTACHMENT_PATH => {
+            let ptr = spPathAttachment_create(name);
+            let mut path = Path::from_raw(NonNull::new_unchecked(ptr));
+            let result = catch_panic(|| loader.create_path(&skin_name, &name_str, &mut path));
+            (ptr as *mut spAttachment, result)
+        }
+        SP_ATTACHMENT_POINT => {
+            let ptr = spPointAttachment_create(name);
+            let mut point = Point::from_raw(NonNull::new_unchecked(ptr));
+            let result = catch_panic(|| loader.create_point(&skin_name, &name_str, &mut point));
+            (ptr as *mut spAttachment, result)
+        }
+        SP_ATTACHMENT_CLIPPING => {
+            let ptr = spClippingAttachment_create(name);
+            let mut clipping = Clipping::from_raw(NonNull::new_unchecked(ptr));
+            let result =
+                catch_panic(|| loader.create_clipping(&skin_name, &name_str, &mut clipping));
+            (ptr as *mut spAttachment, result)
+        }
+    };
+
+    match result {
+        Some(Ok(true)) => attachment,
+        Some(Ok(false)) => {
+            spAttachment_dispose(attachment);
+            ptr::null_mut()
+        }
+        Some(Err(err)) => {
+            set_error(raw, &err.to_string());
+            spAttachment_dispose(attachment);
+            ptr::null_mut()
+        }
+        // stops the reading, the panic is resumed once spine-c returns
+        None => {
+            set_error(raw, "attachment loader panicked");
+            spAttachment_dispose(attachment);
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn configure_attachment(
+    raw: *mut spAttachmentLoader,
+    attachment: *mut spAttachment,
+) {
+    if let Some(attachment) = NonNull::new(attachment) {
+        catch_panic(|| loader(raw).configure(&Attachment::from_raw(attachment)));
+    }
+}
+
+unsafe extern "C" fn dispose_attachment(
+    raw: *mut spAttachmentLoader,
+    attachment: *mut spAttachment,
+) {
+    if let Some(attachment) = NonNull::new(attachment) {
+        catch_panic(|| loader(raw).dispose(&Attachment::from_raw(attachment)));
+    }
+}
@@ -1,9 +1,11 @@
 use super::vertex::Vertex;
 use atlas::region::Region as AtlasRegion;
-use common::from_raw_buf;
+use color::Color;
+use common::{from_raw_buf, from_raw_buf_or_empty, to_opt_string};
 use libspine_sys::*;
 use raw::*;
 use slot::Slot;
+use std::os::raw::{c_int, c_void};
 
 pub struct Mesh {
     raw: NonNull<spMeshAttachment>,
@@ -24,10 +26,33 @@ impl Mesh {
         }
     }
 
-    pub fn atlas_region(&self) -> Option<AtlasRegion> {
+    pub fn atlas_region(&self) -> Option<AtlasRegion<'_>> {
         let ptr = self.as_raw().rendererObject as *mut spAtlasRegion;
 
-        NonNull::new(ptr).map(|raw| AtlasRegion::from_raw(raw))
+        NonNull::new(ptr).map(AtlasRegion::from_raw)
+    }
+
+    // what spine-c's atlas attachment loader does, for use in an `AttachmentLoader`
+    pub fn set_atlas_region(&mut self, region: &AtlasRegion) {
+        let (u, v, u2, v2) = region.uvs();
+        let (offset_x, offset_y) = region.offset();
+        let (original_width, original_height) = region.original_size();
+
+        unsafe {
+            let raw = self.as_raw_mut();
+            (*raw).rendererObject = region.as_raw() as *const _ as *mut c_void;
+            (*raw).regionU = u;
+            (*raw).regionV = v;
+            (*raw).regionU2 = u2;
+            (*raw).regionV2 = v2;
+            (*raw).regionRotate = region.rotate() as c_int;
+            (*raw).regionOffsetX = offset_x;
+            (*raw).regionOffsetY = offset_y;
+            (*raw).regionWidth = region.width();
+            (*raw).regionHeight = region.height();
+            (*raw).regionOriginalWidth = original_width;
+            (*raw).regionOriginalHeight = original_height;
+        }
     }
 
     pub fn triangles(&self) -> Vec<u16> {
@@ -36,34 +61,70 @@ impl Mesh {
             from_raw_buf(self.as_raw().triangles, len)
         }
     }
-}
 
-impl Vertex for Mesh {
-    fn world_vertices_len(&self) -> usize {
-        self.as_raw().super_.worldVerticesLength as usize
+    pub fn path(&self) -> Option<String> {
+        unsafe { to_opt_string(self.as_raw().path) }
     }
 
-    fn compute_world_vertices(
-        &self,
-        slot: &Slot,
-        start: i32,
-        count: i32,
-        vertices: &mut Vec<f32>,
-        offset: i32,
-        stride: i32,
-    ) {
-        unsafe {
-            let vertex: *const _ = &self.as_raw().super_;
-            let slot: *const _ = slot.as_raw();
-            spVertexAttachment_computeWorldVertices(
-                vertex as *mut spVertexAttachment,
-                slot as *mut spSlot,
-                start,
-                count,
-                vertices.as_mut_ptr(),
-                offset,
-                stride,
-            );
-        }
+    // only set for linked meshes, which share vertices and triangles with their parent
+    pub fn parent_mesh(&self) -> Option<Mesh> {
+        NonNull::new(self.as_raw().parentMesh).map(Mesh::from_raw)
+    }
+
+    pub fn inherit_deform(&self) -> bool {
+        self.as_raw().inheritDeform != 0
+    }
+
+    pub fn hull_length(&self) -> i32 {
+        self.as_raw().hullLength
+    }
+
+    // only exported by spine with nonessential data
+    pub fn edges(&self) -> Vec<i32> {
+        let raw = self.as_raw();
+
+        unsafe { from_raw_buf_or_empty(raw.edges, raw.edgesCount) }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.as_raw().width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.as_raw().height
+    }
+
+    pub fn color(&self) -> Color {
+        Color::from(self.as_raw().color)
+    }
+
+    pub fn region_uvs(&self) -> (f32, f32, f32, f32) {
+        let raw = self.as_raw();
+
+        (raw.regionU, raw.regionV, raw.regionU2, raw.regionV2)
+    }
+
+    pub fn region_rotate(&self) -> bool {
+        self.as_raw().regionRotate != 0
+    }
+
+    pub fn region_offset(&self) -> (i32, i32) {
+        let raw = self.as_raw();
+
+        (raw.regionOffsetX, raw.regionOffsetY)
+    }
+
+    pub fn region_size(&self) -> (i32, i32) {
+        let raw = self.as_raw();
+
+        (raw.regionWidth, raw.regionHeight)
+    }
+
+    pub fn region_original_size(&self) -> (i32, i32) {
+        let raw = self.as_raw();
+
+        (raw.regionOriginalWidth, raw.regionOriginalHeight)
     }
 }
+
+impl_vertex!(Mesh);
@@ -3,8 +3,47 @@ use libspine_sys::*;
 use raw::*;
 use std::ffi::CStr;
 
+// every vertex attachment embeds an `spVertexAttachment` as `super_`
+macro_rules! impl_vertex {
+    ($t:ident) => {
+        impl Vertex for $t {
+            fn world_vertices_len(&self) -> usize {
+                self.as_raw().super_.worldVerticesLength as usize
+            }
+
+            fn check_deform(&self, slot: &Slot) -> Result<(), Error> {
+                super::vertex::check_deform(&self.as_raw().super_, slot.as_raw())
+            }
+
+            unsafe fn compute_world_vertices_unchecked(
+                &self,
+                slot: &Slot,
+                start: usize,
+                count: usize,
+                vertices: *mut f32,
+                offset: usize,
+                stride: usize,
+            ) {
+                let vertex: *const _ = &self.as_raw().super_;
+                let slot: *const _ = slot.as_raw();
+                spVertexAttachment_computeWorldVertices(
+                    vertex as *mut spVertexAttachment,
+                    slot as *mut spSlot,
+                    start as i32,
+                    count as i32,
+                    vertices,
+                    offset as i32,
+                    stride as i32,
+                );
+            }
+        }
+    };
+}
+
+pub mod atlas_loader;
 pub mod bounding_box;
 pub mod clipping;
+pub mod loader;
 pub mod mesh;
 pub mod path;
 pub mod point;
@@ -82,8 +121,14 @@ impl Attachment {
             SP_ATTACHMENT_BOUNDING_BOX => {
                 create_attachment!(BoundingBox, spBoundingBoxAttachment, raw)
             }
+            // spine-c creates linked meshes as plain meshes with a parent
             SP_ATTACHMENT_MESH | SP_ATTACHMENT_LINKED_MESH => {
-                create_attachment!(Mesh, spMeshAttachment, raw)
+                let mesh = Mesh::from_raw(raw.cast::<spMeshAttachment>());
+                if mesh.parent_mesh().is_some() {
+                    Attachment::LinkedMesh(mesh)
+                } else {
+                    Attachment::Mesh(mesh)
+                }
             }
             SP_ATTACHMENT_PATH => create_attachment!(Path, spPathAttachment, raw),
             SP_ATTACHMENT_POINT => create_attachment!(Point, spPointAttachment, raw),
@@ -110,5 +155,9 @@ impl_attachment!(Clipping);
 
 pub trait Base {
     fn name(&self) -> Option<String>;
+
+    /// # Safety
+    ///
+    /// The attachment must not have been freed with its skin or skeleton data.
     unsafe fn raw_type(&self) -> AttachmentType;
 }
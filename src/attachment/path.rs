@@ -1,5 +1,8 @@
-use libspine_sys::spPathAttachment;
+use super::vertex::Vertex;
+use common::from_raw_buf_or_empty;
+use libspine_sys::*;
 use raw::*;
+use slot::Slot;
 
 pub struct Path {
     raw: NonNull<spPathAttachment>,
@@ -11,7 +14,23 @@ impl Path {
             raw
         }
     }
+
+    pub fn closed(&self) -> bool {
+        self.as_raw().closed != 0
+    }
+
+    pub fn constant_speed(&self) -> bool {
+        self.as_raw().constantSpeed != 0
+    }
+
+    // length of each curve, in setup pose
+    pub fn lengths(&self) -> Vec<f32> {
+        let raw = self.as_raw();
+
+        unsafe { from_raw_buf_or_empty(raw.lengths, raw.lengthsLength) }
+    }
 }
 
 impl_as_raw!(Path, raw, spPathAttachment);
 impl_as_raw_mut!(Path, raw);
+impl_vertex!(Path);
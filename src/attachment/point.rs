@@ -1,4 +1,6 @@
-use libspine_sys::spPointAttachment;
+use bone::Bone;
+use color::Color;
+use libspine_sys::*;
 use raw::*;
 
 pub struct Point {
@@ -11,6 +13,46 @@ impl Point {
             raw
         }
     }
+
+    pub fn x(&self) -> f32 {
+        self.as_raw().x
+    }
+
+    pub fn y(&self) -> f32 {
+        self.as_raw().y
+    }
+
+    pub fn rotation(&self) -> f32 {
+        self.as_raw().rotation
+    }
+
+    pub fn color(&self) -> Color {
+        Color::from(self.as_raw().color)
+    }
+
+    pub fn compute_world_position(&self, bone: &Bone) -> (f32, f32) {
+        let (mut x, mut y) = (0., 0.);
+        unsafe {
+            spPointAttachment_computeWorldPosition(
+                self.as_raw() as *const _ as *mut spPointAttachment,
+                bone.as_raw() as *const _ as *mut spBone,
+                &mut x,
+                &mut y,
+            );
+        }
+
+        (x, y)
+    }
+
+    // in degrees
+    pub fn compute_world_rotation(&self, bone: &Bone) -> f32 {
+        unsafe {
+            spPointAttachment_computeWorldRotation(
+                self.as_raw() as *const _ as *mut spPointAttachment,
+                bone.as_raw() as *const _ as *mut spBone,
+            )
+        }
+    }
 }
 
 impl_as_raw!(Point, raw, spPointAttachment);
@@ -1,7 +1,27 @@
+use super::vertex::{check_world_vertices, to_points};
 use atlas::region::Region as AtlasRegion;
 use bone::Bone;
+use color::Color;
+use common::to_opt_string;
 use libspine_sys::*;
 use raw::*;
+use std::os::raw::{c_int, c_void};
+
+// setters recompute the quad offsets, as spine-c only does that while loading
+macro_rules! region_property {
+    ($get:ident, $set:ident, $field:ident) => {
+        pub fn $get(&self) -> f32 {
+            self.as_raw().$field
+        }
+
+        pub fn $set(&mut self, value: f32) {
+            unsafe {
+                (*self.as_raw_mut()).$field = value;
+            }
+            self.update_offset();
+        }
+    };
+}
 
 pub struct Region {
     raw: NonNull<spRegionAttachment>,
@@ -15,33 +35,122 @@ impl Region {
         Region { raw }
     }
 
+    region_property!(x, set_x, x);
+    region_property!(y, set_y, y);
+    region_property!(rotation, set_rotation, rotation);
+    region_property!(scale_x, set_scale_x, scaleX);
+    region_property!(scale_y, set_scale_y, scaleY);
+    region_property!(width, set_width, width);
+    region_property!(height, set_height, height);
+
+    pub fn color(&self) -> Color {
+        Color::from(self.as_raw().color)
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        unsafe {
+            (*self.as_raw_mut()).color = color.into();
+        }
+    }
+
+    pub fn path(&self) -> Option<String> {
+        unsafe { to_opt_string(self.as_raw().path) }
+    }
+
+    // local corner positions of the quad, as computed by `update_offset`
+    pub fn offset(&self) -> [f32; 8] {
+        self.as_raw().offset
+    }
+
+    pub fn update_offset(&mut self) {
+        unsafe {
+            spRegionAttachment_updateOffset(self.as_raw_mut());
+        }
+    }
+
     pub fn uvs(&self) -> [f32; 8] {
         self.as_raw().uvs
     }
 
-    pub fn atlas_region(&self) -> Option<AtlasRegion> {
+    pub fn set_uvs(&mut self, u: f32, v: f32, u2: f32, v2: f32, rotate: bool) {
+        unsafe {
+            spRegionAttachment_setUVs(self.as_raw_mut(), u, v, u2, v2, rotate as c_int);
+        }
+    }
+
+    pub fn atlas_region(&self) -> Option<AtlasRegion<'_>> {
         let ptr = self.as_raw().rendererObject as *mut spAtlasRegion;
 
-        NonNull::new(ptr).map(|raw| AtlasRegion::from_raw(raw))
+        NonNull::new(ptr).map(AtlasRegion::from_raw)
+    }
+
+    // what spine-c's atlas attachment loader does, also swaps the region of a loaded attachment
+    pub fn set_atlas_region(&mut self, region: &AtlasRegion) {
+        let (u, v, u2, v2) = region.uvs();
+        let (offset_x, offset_y) = region.offset();
+        let (original_width, original_height) = region.original_size();
+
+        unsafe {
+            let raw = self.as_raw_mut();
+            (*raw).rendererObject = region.as_raw() as *const _ as *mut c_void;
+            spRegionAttachment_setUVs(raw, u, v, u2, v2, region.rotate() as c_int);
+            (*raw).regionOffsetX = offset_x;
+            (*raw).regionOffsetY = offset_y;
+            (*raw).regionWidth = region.width();
+            (*raw).regionHeight = region.height();
+            (*raw).regionOriginalWidth = original_width;
+            (*raw).regionOriginalHeight = original_height;
+        }
+        self.update_offset();
     }
 
     pub fn compute_world_vertices(
         &self,
         bone: &Bone,
-        vertices: &mut Vec<f32>,
-        offset: i32,
-        stride: i32,
-    ) {
+        vertices: &mut [f32],
+        offset: usize,
+        stride: usize,
+    ) -> Result<(), Error> {
+        check_world_vertices(8, 0, 8, vertices.len(), offset, stride)?;
+
+        unsafe {
+            self.compute_world_vertices_unchecked(bone, vertices.as_mut_ptr(), offset, stride);
+        }
+
+        Ok(())
+    }
+
+    // resizes `vertices` to the four corners, tightly packed
+    pub fn compute_world_vertices_vec(&self, bone: &Bone, vertices: &mut Vec<f32>) {
+        vertices.resize(8, 0.);
+
         unsafe {
-            let self_: *const _ = self.as_raw();
-            let bone: *const _ = bone.as_raw();
-            spRegionAttachment_computeWorldVertices(
-                self_ as *mut spRegionAttachment,
-                bone as *mut spBone,
-                vertices.as_mut_ptr(),
-                offset,
-                stride,
-            );
+            self.compute_world_vertices_unchecked(bone, vertices.as_mut_ptr(), 0, 2);
         }
     }
+
+    pub fn world_vertices(&self, bone: &Bone) -> Vec<[f32; 2]> {
+        let mut vertices = Vec::new();
+        self.compute_world_vertices_vec(bone, &mut vertices);
+
+        to_points(&vertices)
+    }
+
+    unsafe fn compute_world_vertices_unchecked(
+        &self,
+        bone: &Bone,
+        vertices: *mut f32,
+        offset: usize,
+        stride: usize,
+    ) {
+        let self_: *const _ = self.as_raw();
+        let bone: *const _ = bone.as_raw();
+        spRegionAttachment_computeWorldVertices(
+            self_ as *mut spRegionAttachment,
+            bone as *mut spBone,
+            vertices,
+            offset as i32,
+            stride as i32,
+        );
+    }
 }
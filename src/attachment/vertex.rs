@@ -1,14 +1,160 @@
+use error::Error;
+use libspine_sys::*;
 use slot::Slot;
+use std::ptr;
 
+// `start` and `count` address floats of the attachment's world vertices, each computed
+// vertex is written as an x, y pair at `offset`, `offset + stride` and so on.
 pub trait Vertex {
     fn world_vertices_len(&self) -> usize;
-    fn compute_world_vertices(
+
+    // the deform of `slot` must cover every float spine-c reads from it, see `check_deform`
+    fn check_deform(&self, slot: &Slot) -> Result<(), Error>;
+
+    /// No bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `vertices` must hold every written float and the deform of `slot` has to pass
+    /// `check_deform`.
+    unsafe fn compute_world_vertices_unchecked(
         &self,
         slot: &Slot,
-        start: i32,
-        count: i32,
-        vertices: &mut Vec<f32>,
-        offset: i32,
-        stride: i32,
+        start: usize,
+        count: usize,
+        vertices: *mut f32,
+        offset: usize,
+        stride: usize,
     );
+
+    fn compute_world_vertices(
+        &self,
+        slot: &Slot,
+        start: usize,
+        count: usize,
+        vertices: &mut [f32],
+        offset: usize,
+        stride: usize,
+    ) -> Result<(), Error> {
+        check_world_vertices(
+            self.world_vertices_len(),
+            start,
+            count,
+            vertices.len(),
+            offset,
+            stride,
+        )?;
+        self.check_deform(slot)?;
+
+        unsafe {
+            self.compute_world_vertices_unchecked(
+                slot,
+                start,
+                count,
+                vertices.as_mut_ptr(),
+                offset,
+                stride,
+            );
+        }
+
+        Ok(())
+    }
+
+    // resizes `vertices` to hold every world vertex, tightly packed. Panics like an out of
+    // range index when `slot` deforms another attachment with fewer vertices.
+    fn compute_world_vertices_vec(&self, slot: &Slot, vertices: &mut Vec<f32>) {
+        if let Err(err) = self.check_deform(slot) {
+            panic!("{}", err);
+        }
+
+        let len = self.world_vertices_len();
+        vertices.resize(len, 0.);
+
+        unsafe {
+            self.compute_world_vertices_unchecked(slot, 0, len, vertices.as_mut_ptr(), 0, 2);
+        }
+    }
+
+    fn world_vertices(&self, slot: &Slot) -> Vec<[f32; 2]> {
+        let mut vertices = Vec::new();
+        self.compute_world_vertices_vec(slot, &mut vertices);
+
+        to_points(&vertices)
+    }
+}
+
+pub(crate) fn check_world_vertices(
+    len: usize,
+    start: usize,
+    count: usize,
+    buffer_len: usize,
+    offset: usize,
+    stride: usize,
+) -> Result<(), Error> {
+    // spine-c takes every argument and computes every index as an `int`
+    let max = i32::MAX as usize;
+    let in_range = start
+        .checked_add(count)
+        .map(|end| end <= len && end <= max)
+        .unwrap_or(false);
+    if (start | count) & 1 != 0 || !in_range {
+        return Err(Error::InvalidVertexRange { start, count, len });
+    }
+    if stride < 2 || stride > max {
+        return Err(Error::InvalidStride(stride));
+    }
+
+    if offset > max {
+        return Err(Error::VertexOffsetOverflow { offset, stride });
+    }
+    if count == 0 {
+        return Ok(());
+    }
+
+    let required = (count / 2 - 1)
+        .checked_mul(stride)
+        .and_then(|last| last.checked_add(offset))
+        .and_then(|last| last.checked_add(2));
+    let required = match required {
+        Some(required) if required <= max => required,
+        _ => return Err(Error::VertexOffsetOverflow { offset, stride }),
+    };
+    if required > buffer_len {
+        return Err(Error::VertexBufferTooSmall {
+            required,
+            len: buffer_len,
+        });
+    }
+
+    Ok(())
+}
+
+// spine-c reads the slot's deform whenever it holds any, even when it was keyed for another
+// attachment, so a foreign deform has to be at least as long as this attachment's
+pub(crate) fn check_deform(vertex: &spVertexAttachment, slot: &spSlot) -> Result<(), Error> {
+    let len = slot.attachmentVerticesCount.max(0) as usize;
+    if len == 0 || ptr::eq(slot.attachment, &vertex.super_) {
+        return Ok(());
+    }
+
+    // weighted vertices store x, y and weight per bone, the deform only offsets x and y
+    let required = if vertex.bones.is_null() {
+        vertex.worldVerticesLength
+    } else {
+        vertex.verticesCount / 3 * 2
+    };
+    let required = required.max(0) as usize;
+    if len < required {
+        return Err(Error::DeformTooShort { required, len });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn to_points(vertices: &[f32]) -> Vec<[f32; 2]> {
+    vertices
+        .chunks(2)
+        .filter(|point| point.len() == 2)
+        .map(|point| [point[0], point[1]])
+        .collect()
 }
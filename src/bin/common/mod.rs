@@ -0,0 +1,104 @@
+// shared by the binaries in src/bin, each of them uses a subset
+#![allow(dead_code)]
+
+use image;
+use spiner::extension::Texture;
+use spiner::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+fn read_file(path: &str) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::new();
+    File::open(path).and_then(|mut f| f.read_to_end(&mut buf))?;
+
+    Ok(buf)
+}
+
+fn read_texture(path: &str, texture: &mut Texture) -> Result<(), Error> {
+    let image = image::open(path)
+        .map_err(|err| Error::TextureLoad {
+            path: path.to_owned(),
+            message: err.to_string(),
+        })?
+        .to_rgba();
+    let (width, height) = image.dimensions();
+    texture.buffer = image.into_raw();
+    texture.width = width;
+    texture.height = height;
+
+    Ok(())
+}
+
+extend_spine!({
+    _spUtil_readFile -> read_file,
+    _spAtlasPage_createTexture -> read_texture
+});
+
+pub struct AssetPaths {
+    pub atlas: String,
+    pub skeleton: String,
+}
+
+// `assets/spineboy` resolves to `assets/spineboy/spineboy.{atlas,json}`,
+// `path/name.json` to `path/name.atlas` next to it
+pub fn asset_paths(path: &str) -> AssetPaths {
+    let path = Path::new(path);
+    let base = if path.is_dir() {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        path.join(name)
+    } else {
+        path.with_extension("")
+    };
+
+    AssetPaths {
+        atlas: base.with_extension("atlas").to_string_lossy().into_owned(),
+        skeleton: base.with_extension("json").to_string_lossy().into_owned(),
+    }
+}
+
+pub struct Args {
+    pub positional: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    // `--name value` pairs, except for the given `switches` which take no value
+    pub fn parse<I: Iterator<Item = String>>(mut args: I, switches: &[&str]) -> Self {
+        let mut positional = Vec::new();
+        let mut options: Vec<(String, Option<String>)> = Vec::new();
+
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let name = name.to_owned();
+                let value = if switches.contains(&name.as_str()) {
+                    None
+                } else {
+                    args.next()
+                };
+                options.push((name, value));
+            } else {
+                positional.push(arg);
+            }
+        }
+
+        Args {
+            positional,
+            options,
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(key, _)| key == name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.as_ref().map(|value| value.as_str()))
+    }
+}
@@ -0,0 +1,360 @@
+extern crate image;
+extern crate libc;
+extern crate libspine_sys;
+#[macro_use]
+extern crate serde_json;
+#[macro_use]
+extern crate spiner;
+
+mod common;
+
+use common::{asset_paths, Args};
+use serde_json::Value;
+use spiner::atlas::Atlas;
+use spiner::bone::BoneData;
+use spiner::skeleton::data::Data as SkeletonData;
+use spiner::skeleton::json::{Json as SkeletonJson, Report};
+use spiner::Error;
+use std::env;
+use std::process;
+use std::rc::Rc;
+
+const USAGE: &str = "usage: spiner-inspect <asset dir | skeleton.json> [--atlas <file>] \
+[--scale <n>] [--lenient] [--json]";
+
+fn main() {
+    let args = Args::parse(env::args().skip(1), &["json", "lenient"]);
+    let path = match args.positional.first() {
+        Some(path) => path.clone(),
+        None => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = run(&path, &args) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run(path: &str, args: &Args) -> Result<(), Error> {
+    let mut paths = asset_paths(path);
+    if let Some(atlas) = args.value("atlas") {
+        paths.atlas = atlas.to_owned();
+    }
+    let scale = args
+        .value("scale")
+        .and_then(|scale| scale.parse().ok())
+        .unwrap_or(1.);
+
+    let atlas = Rc::new(Atlas::from_file(&paths.atlas)?);
+    let (data, report) = if args.flag("lenient") {
+        SkeletonJson::lenient(Rc::clone(&atlas), scale)?.read_skeleton_file_with_report(&paths.skeleton)?
+    } else {
+        let data = SkeletonJson::new(&atlas, scale)?.read_skeleton_file(&paths.skeleton)?;
+        (data, Report::default())
+    };
+
+    if args.flag("json") {
+        let report = json!({
+            "atlas": atlas_json(&paths.atlas, &atlas),
+            "skeleton": skeleton_json(&paths.skeleton, &data),
+            "missingRegions": report.missing_regions.iter().map(|missing| json!({
+                "skin": missing.skin,
+                "attachment": missing.attachment,
+                "path": missing.path,
+            })).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    } else {
+        print_atlas(&paths.atlas, &atlas);
+        println!();
+        print_skeleton(&paths.skeleton, &data);
+        print_report(&report);
+    }
+
+    Ok(())
+}
+
+fn print_atlas(path: &str, atlas: &Atlas) {
+    println!("atlas: {}", path);
+    for page in atlas.pages() {
+        println!(
+            "  page {} ({}x{}, {:?}, filter {:?}/{:?}, wrap {:?}/{:?})",
+            page.name,
+            page.width,
+            page.height,
+            page.format,
+            page.sampler.min_filter,
+            page.sampler.mag_filter,
+            page.sampler.u_wrap,
+            page.sampler.v_wrap
+        );
+    }
+    for region in atlas.regions() {
+        let page = region.page().map(|page| page.name).unwrap_or_default();
+        let (original_width, original_height) = region.original_size();
+        println!(
+            "  region {} [{}] {}x{} at {},{}{} (original {}x{})",
+            region.name(),
+            page,
+            region.width(),
+            region.height(),
+            region.x(),
+            region.y(),
+            if region.rotate() { ", rotated" } else { "" },
+            original_width,
+            original_height
+        );
+    }
+}
+
+fn print_skeleton(path: &str, data: &SkeletonData) {
+    let metadata = data.metadata();
+    println!("skeleton: {}", path);
+    println!(
+        "  spine {}, hash {}, size {}x{}",
+        metadata.version.unwrap_or_else(|| "?".to_owned()),
+        metadata.hash.unwrap_or_else(|| "?".to_owned()),
+        metadata.width,
+        metadata.height
+    );
+
+    let bones = data.bones();
+    println!("bones ({}):", bones.len());
+    for bone in bones.iter().filter(|bone| bone.parent.is_none()) {
+        print_bone_tree(&bones, bone, 1);
+    }
+
+    let slots = data.slots();
+    println!("slots ({}):", slots.len());
+    for slot in slots.iter() {
+        println!(
+            "  {} (bone: {}, attachment: {}, blend: {:?})",
+            slot.name,
+            slot.bone,
+            slot.attachment.as_ref().map_or("-", |name| name.as_str()),
+            slot.blend_mode
+        );
+    }
+
+    let skins = data.skins();
+    println!("skins ({}):", skins.len());
+    for skin in skins.iter() {
+        println!("  {} ({} attachments)", skin.name, skin.entries.len());
+        for entry in skin.entries.iter() {
+            let slot = slots
+                .get(entry.slot_index as usize)
+                .map_or("?", |slot| slot.name.as_str());
+            println!("    {}: {}", slot, entry.name);
+        }
+    }
+
+    let animations = data.animations();
+    println!("animations ({}):", animations.len());
+    for animation in animations.iter() {
+        println!("  {} ({:.3}s)", animation.name, animation.duration);
+    }
+
+    let events = data.events();
+    println!("events ({}):", events.len());
+    for event in events.iter() {
+        println!(
+            "  {} (int: {}, float: {}, string: {})",
+            event.name,
+            event.int_value,
+            event.float_value,
+            event.string_value.as_ref().map_or("-", |value| value.as_str())
+        );
+    }
+
+    let ik_constraints = data.ik_constraints();
+    println!("ik constraints ({}):", ik_constraints.len());
+    for ik in ik_constraints.iter() {
+        println!(
+            "  {} ({} -> {}, mix {}, bend {})",
+            ik.name,
+            ik.bones.join(", "),
+            ik.target,
+            ik.mix,
+            ik.bend_direction
+        );
+    }
+
+    let transform_constraints = data.transform_constraints();
+    println!("transform constraints ({}):", transform_constraints.len());
+    for constraint in transform_constraints.iter() {
+        println!(
+            "  {} ({} -> {}, mix rotate {} translate {} scale {} shear {})",
+            constraint.name,
+            constraint.bones.join(", "),
+            constraint.target,
+            constraint.rotate_mix,
+            constraint.translate_mix,
+            constraint.scale_mix,
+            constraint.shear_mix
+        );
+    }
+
+    let path_constraints = data.path_constraints();
+    println!("path constraints ({}):", path_constraints.len());
+    for constraint in path_constraints.iter() {
+        println!(
+            "  {} ({} -> {}, mix rotate {} translate {})",
+            constraint.name,
+            constraint.bones.join(", "),
+            constraint.target,
+            constraint.rotate_mix,
+            constraint.translate_mix
+        );
+    }
+}
+
+fn print_report(report: &Report) {
+    if report.is_empty() {
+        return;
+    }
+
+    println!("missing regions ({}):", report.missing_region_names().len());
+    for missing in report.missing_regions.iter() {
+        println!("  {} (skin: {}, attachment: {})", missing.path, missing.skin, missing.attachment);
+    }
+}
+
+fn print_bone_tree(bones: &[BoneData], bone: &BoneData, depth: usize) {
+    println!("{}{} (length {})", "  ".repeat(depth), bone.name, bone.length);
+
+    let children = bones
+        .iter()
+        .filter(|child| child.parent.as_ref() == Some(&bone.name));
+    for child in children {
+        print_bone_tree(bones, child, depth + 1);
+    }
+}
+
+fn atlas_json(path: &str, atlas: &Atlas) -> Value {
+    let pages: Vec<Value> = atlas
+        .pages()
+        .map(|page| {
+            json!({
+                "name": page.name,
+                "width": page.width,
+                "height": page.height,
+                "format": format!("{:?}", page.format),
+                "minFilter": format!("{:?}", page.sampler.min_filter),
+                "magFilter": format!("{:?}", page.sampler.mag_filter),
+                "uWrap": format!("{:?}", page.sampler.u_wrap),
+                "vWrap": format!("{:?}", page.sampler.v_wrap),
+            })
+        })
+        .collect();
+    let regions: Vec<Value> = atlas
+        .regions()
+        .iter()
+        .map(|region| {
+            let (u, v, u2, v2) = region.uvs();
+            let (offset_x, offset_y) = region.offset();
+            let (original_width, original_height) = region.original_size();
+
+            json!({
+                "name": region.name(),
+                "page": region.page().map(|page| page.name),
+                "index": region.index(),
+                "x": region.x(),
+                "y": region.y(),
+                "width": region.width(),
+                "height": region.height(),
+                "uvs": [u, v, u2, v2],
+                "rotate": region.rotate(),
+                "degrees": region.degrees(),
+                "offsetX": offset_x,
+                "offsetY": offset_y,
+                "originalWidth": original_width,
+                "originalHeight": original_height,
+                "splits": region.splits(),
+                "pads": region.pads(),
+            })
+        })
+        .collect();
+
+    json!({
+        "path": path,
+        "pages": pages,
+        "regions": regions,
+    })
+}
+
+fn skeleton_json(path: &str, data: &SkeletonData) -> Value {
+    let metadata = data.metadata();
+    let slots = data.slots();
+
+    json!({
+        "path": path,
+        "spine": metadata.version,
+        "hash": metadata.hash,
+        "width": metadata.width,
+        "height": metadata.height,
+        "bones": data.bones().iter().map(|bone| json!({
+            "name": bone.name,
+            "parent": bone.parent,
+            "length": bone.length,
+            "x": bone.x,
+            "y": bone.y,
+            "rotation": bone.rotation,
+            "scaleX": bone.scale_x,
+            "scaleY": bone.scale_y,
+            "shearX": bone.shear_x,
+            "shearY": bone.shear_y,
+        })).collect::<Vec<_>>(),
+        "slots": slots.iter().map(|slot| json!({
+            "name": slot.name,
+            "bone": slot.bone,
+            "attachment": slot.attachment,
+            "blend": format!("{:?}", slot.blend_mode),
+        })).collect::<Vec<_>>(),
+        "skins": data.skins().iter().map(|skin| json!({
+            "name": skin.name,
+            "attachments": skin.entries.iter().map(|entry| json!({
+                "slot": slots.get(entry.slot_index as usize).map(|slot| slot.name.clone()),
+                "name": entry.name,
+            })).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+        "animations": data.animations().iter().map(|animation| json!({
+            "name": animation.name,
+            "duration": animation.duration,
+        })).collect::<Vec<_>>(),
+        "events": data.events().iter().map(|event| json!({
+            "name": event.name,
+            "int": event.int_value,
+            "float": event.float_value,
+            "string": event.string_value,
+        })).collect::<Vec<_>>(),
+        "ikConstraints": data.ik_constraints().iter().map(|ik| json!({
+            "name": ik.name,
+            "order": ik.order,
+            "bones": ik.bones,
+            "target": ik.target,
+            "mix": ik.mix,
+            "bendPositive": ik.bend_direction > 0,
+        })).collect::<Vec<_>>(),
+        "transformConstraints": data.transform_constraints().iter().map(|constraint| json!({
+            "name": constraint.name,
+            "order": constraint.order,
+            "bones": constraint.bones,
+            "target": constraint.target,
+            "rotateMix": constraint.rotate_mix,
+            "translateMix": constraint.translate_mix,
+            "scaleMix": constraint.scale_mix,
+            "shearMix": constraint.shear_mix,
+        })).collect::<Vec<_>>(),
+        "pathConstraints": data.path_constraints().iter().map(|constraint| json!({
+            "name": constraint.name,
+            "order": constraint.order,
+            "bones": constraint.bones,
+            "target": constraint.target,
+            "rotateMix": constraint.rotate_mix,
+            "translateMix": constraint.translate_mix,
+        })).collect::<Vec<_>>(),
+    })
+}
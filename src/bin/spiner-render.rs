@@ -0,0 +1,219 @@
+extern crate gif;
+extern crate image;
+extern crate libc;
+extern crate libspine_sys;
+#[macro_use]
+extern crate spiner;
+
+mod common;
+
+use common::{asset_paths, Args};
+use gif::SetParameter;
+use spiner::animation::state::{State as AnimationState, StateData};
+use spiner::atlas::Atlas;
+use spiner::debug::{self, Geometry, Options as DebugOptions};
+use spiner::render::software::{draw_batch, draw_debug, Camera, Canvas};
+use spiner::render::{Batch, Batcher, Bounds};
+use spiner::skeleton::json::Json as SkeletonJson;
+use spiner::skeleton::Skeleton;
+use spiner::Error;
+use std::env;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::process;
+use std::rc::Rc;
+
+const USAGE: &str = "usage: spiner-render <asset dir | skeleton.json> --out <dir | file.gif> \
+[--animation <name>] [--skin <name>] [--fps <n>] [--width <px>] [--height <px>] \
+[--padding <px>] [--background <rrggbb[aa]>] [--atlas <file>] [--scale <n>] [--lenient] [--debug]";
+
+fn main() {
+    let args = Args::parse(env::args().skip(1), &["lenient", "debug"]);
+    let (path, out) = match (args.positional.first(), args.value("out")) {
+        (Some(path), Some(out)) => (path.clone(), out.to_owned()),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = run(&path, &out, &args) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run(path: &str, out: &str, args: &Args) -> Result<(), Error> {
+    let mut paths = asset_paths(path);
+    if let Some(atlas) = args.value("atlas") {
+        paths.atlas = atlas.to_owned();
+    }
+    let scale = parse_or(args, "scale", 1.);
+    let fps = parse_or(args, "fps", 30.).max(1.);
+    let padding = parse_or(args, "padding", 16.).max(0.);
+
+    let atlas = Rc::new(Atlas::from_file(&paths.atlas)?);
+    atlas.check_textures()?;
+    let data = if args.flag("lenient") {
+        let json = SkeletonJson::lenient(Rc::clone(&atlas), scale)?;
+        let (data, report) = json.read_skeleton_file_with_report(&paths.skeleton)?;
+        for name in report.missing_region_names() {
+            eprintln!("warning: missing region {}, drawn as placeholder", name);
+        }
+        Rc::new(data)
+    } else {
+        Rc::new(SkeletonJson::new(&atlas, scale)?.read_skeleton_file(&paths.skeleton)?)
+    };
+    let animation = match args.value("animation") {
+        Some(name) => data.find_animation_by_name(name),
+        None => data.animations().into_iter().next(),
+    }.ok_or_else(|| Error::AnimationNotFound(args.value("animation").unwrap_or("").to_owned()))?;
+
+    let mut skeleton = Skeleton::from_data(Rc::clone(&data))?;
+    if let Some(skin) = args.value("skin") {
+        skeleton.set_skin(skin)?;
+        skeleton.set_slots_to_setup_pose();
+    }
+    let state_data = Rc::new(StateData::from_skeleton_data(Rc::clone(&data))?);
+    let mut state = AnimationState::from_data(state_data)?;
+    state.set_animation(0, &animation, true)?;
+
+    // pose every frame first, so that framing covers the whole animation
+    let frames_count = ((animation.duration * fps).ceil() as usize).max(1);
+    let mut batcher = Batcher::new();
+    let mut bounds = Bounds::empty();
+    let mut frames: Vec<(Vec<Batch>, Option<Geometry>)> = Vec::with_capacity(frames_count);
+    for frame in 0..frames_count {
+        if frame > 0 {
+            state.update(1. / fps);
+        }
+        state.apply(&mut skeleton)?;
+        skeleton.update_world_transform();
+
+        let batches = batcher.batch(&skeleton);
+        bounds.merge(&Bounds::from_batches(&batches));
+        let geometry = if args.flag("debug") {
+            Some(debug::geometry(&skeleton, &DebugOptions::default()))
+        } else {
+            None
+        };
+        frames.push((batches, geometry));
+    }
+
+    let (width, height) = canvas_size(args, &bounds, padding)?;
+    let camera = Camera::fit(&bounds, width, height, padding);
+    let background = args
+        .value("background")
+        .and_then(parse_color)
+        .unwrap_or([0; 4]);
+
+    let mut blank = Canvas::new(width, height)?;
+    blank.clear(background);
+
+    let canvases = frames.iter().map(|(batches, geometry)| {
+        let mut canvas = blank.clone();
+        for batch in batches.iter() {
+            draw_batch(batch, &camera, &mut canvas);
+        }
+        if let Some(ref geometry) = *geometry {
+            draw_debug(geometry, &camera, &mut canvas);
+        }
+        canvas
+    });
+
+    if out.ends_with(".gif") {
+        write_gif(out, canvases, width, height, fps)
+    } else {
+        write_png_sequence(out, canvases, width, height)
+    }
+}
+
+fn parse_or(args: &Args, name: &str, default: f32) -> f32 {
+    args.value(name)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+// Without an explicit size the canvas keeps the aspect ratio of the animation bounds. Sizes
+// are limited to what a gif frame can hold, for png sequences as well.
+fn canvas_size(args: &Args, bounds: &Bounds, padding: f32) -> Result<(u32, u32), Error> {
+    let width = args.value("width").and_then(|value| value.parse().ok());
+    let height = args.value("height").and_then(|value| value.parse().ok());
+    let aspect = bounds.height().max(1.) / bounds.width().max(1.);
+    let inner = |size: u32| (size as f32 - padding * 2.).max(1.);
+    let outer = |size: f32| (size + padding * 2.).ceil() as u32;
+
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, outer(inner(width) * aspect)),
+        (None, Some(height)) => (outer(inner(height) / aspect), height),
+        (None, None) => (512, outer(inner(512) * aspect)),
+    };
+
+    let max = u32::from(u16::MAX);
+    if width > max || height > max {
+        return Err(Error::from(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "canvas size {}x{} exceeds the maximum of {}x{}",
+                width, height, max, max
+            ),
+        )));
+    }
+
+    Ok((width, height))
+}
+
+fn parse_color(hex: &str) -> Option<[u8; 4]> {
+    let hex = hex.trim_start_matches('#');
+    // also keeps the slicing below on char boundaries
+    if (hex.len() != 6 && hex.len() != 8) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let mut color = [255; 4];
+    for (i, channel) in color.iter_mut().enumerate().take(hex.len() / 2) {
+        *channel = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    Some(color)
+}
+
+fn write_png_sequence<I>(out: &str, canvases: I, width: u32, height: u32) -> Result<(), Error>
+where
+    I: Iterator<Item = Canvas>,
+{
+    fs::create_dir_all(out)?;
+
+    for (frame, canvas) in canvases.enumerate() {
+        let path = Path::new(out).join(format!("frame_{:04}.png", frame));
+        image::save_buffer(&path, &canvas.pixels, width, height, image::ColorType::RGBA(8))?;
+    }
+
+    Ok(())
+}
+
+fn write_gif<I>(out: &str, canvases: I, width: u32, height: u32, fps: f32) -> Result<(), Error>
+where
+    I: Iterator<Item = Canvas>,
+{
+    if let Some(dir) = Path::new(out).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let file = File::create(out)?;
+    let mut encoder = gif::Encoder::new(file, width as u16, height as u16, &[])?;
+    encoder.set(gif::Repeat::Infinite)?;
+
+    // gif delays are in hundredths of a second
+    let delay = (100. / fps).round().max(1.) as u16;
+    for mut canvas in canvases {
+        let mut frame = gif::Frame::from_rgba(width as u16, height as u16, &mut canvas.pixels);
+        frame.delay = delay;
+        frame.dispose = gif::DisposalMethod::Background;
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
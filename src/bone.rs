@@ -1,6 +1,10 @@
-use libspine_sys::spBone;
+use common::to_string;
+use libspine_sys::{spBone, spBoneData, spTransformMode};
+use math::Affine;
 use raw::*;
 
+pub type TransformMode = spTransformMode;
+
 pub struct Bone {
     raw: NonNull<spBone>
 }
@@ -14,4 +18,89 @@ impl Bone {
             raw
         }
     }
+
+    pub fn name(&self) -> String {
+        unsafe { self.data().map_or_else(String::new, |data| to_string(data.name)) }
+    }
+
+    pub fn length(&self) -> f32 {
+        unsafe { self.data().map_or(0., |data| data.length) }
+    }
+
+    pub fn world_x(&self) -> f32 {
+        self.as_raw().worldX
+    }
+
+    pub fn world_y(&self) -> f32 {
+        self.as_raw().worldY
+    }
+
+    // local rotation in degrees, as posed by the animation state
+    pub fn rotation(&self) -> f32 {
+        self.as_raw().rotation
+    }
+
+    // world transform matrix, the columns are the bone's local x and y axes
+    pub fn world_matrix(&self) -> (f32, f32, f32, f32) {
+        let raw = self.as_raw();
+
+        (raw.a, raw.b, raw.c, raw.d)
+    }
+
+    pub fn index(&self) -> i32 {
+        unsafe { self.data().map_or(-1, |data| data.index) }
+    }
+
+    // maps bone local coordinates to world coordinates
+    pub fn world_transform(&self) -> Affine {
+        let raw = self.as_raw();
+
+        Affine::new(raw.a, raw.b, raw.c, raw.d, raw.worldX, raw.worldY)
+    }
+
+    unsafe fn data(&self) -> Option<&spBoneData> {
+        self.as_raw().data.as_ref()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BoneData {
+    pub index: i32,
+    pub name: String,
+    pub parent: Option<String>,
+    pub length: f32,
+    pub x: f32,
+    pub y: f32,
+    pub rotation: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub shear_x: f32,
+    pub shear_y: f32,
+    pub transform_mode: TransformMode,
+}
+
+impl<'a> From<&'a spBoneData> for BoneData {
+    fn from(raw_ref: &'a spBoneData) -> Self {
+        let (name, parent) = unsafe {
+            (
+                to_string(raw_ref.name),
+                raw_ref.parent.as_ref().map(|parent| to_string(parent.name)),
+            )
+        };
+
+        BoneData {
+            index: raw_ref.index,
+            name,
+            parent,
+            length: raw_ref.length,
+            x: raw_ref.x,
+            y: raw_ref.y,
+            rotation: raw_ref.rotation,
+            scale_x: raw_ref.scaleX,
+            scale_y: raw_ref.scaleY,
+            shear_x: raw_ref.shearX,
+            shear_y: raw_ref.shearY,
+            transform_mode: raw_ref.transformMode,
+        }
+    }
 }
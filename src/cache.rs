@@ -0,0 +1,185 @@
+use atlas::Atlas;
+use error::Error;
+use skeleton::data::Data as SkeletonData;
+use skeleton::json::Json as SkeletonJson;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Usage {
+    // times the entry was handed out from the cache instead of being loaded
+    pub hits: u64,
+    // handles alive outside of the cache
+    pub handles: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub atlases: usize,
+    pub skeletons: usize,
+    pub loads: u64,
+    pub hits: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SkeletonKey {
+    skeleton: String,
+    atlas: String,
+    // compared bitwise, scales are expected to come from the same literal or setting
+    scale: u32,
+}
+
+struct Entry<T> {
+    value: Rc<T>,
+    hits: u64,
+}
+
+impl<T> Entry<T> {
+    fn new(value: T) -> Self {
+        Entry {
+            value: Rc::new(value),
+            hits: 0,
+        }
+    }
+
+    fn hit(&mut self) -> Rc<T> {
+        self.hits += 1;
+        Rc::clone(&self.value)
+    }
+
+    fn usage(&self) -> Usage {
+        Usage {
+            hits: self.hits,
+            handles: Rc::strong_count(&self.value) - 1,
+        }
+    }
+
+    fn is_unused(&self) -> bool {
+        Rc::strong_count(&self.value) == 1
+    }
+}
+
+// Loads every atlas and skeleton once and shares them through `Rc` handles. Skeleton data
+// keeps its atlas alive, so handles stay valid after eviction or after the cache is dropped.
+#[derive(Default)]
+pub struct AssetCache {
+    atlases: HashMap<String, Entry<Atlas>>,
+    skeletons: HashMap<SkeletonKey, Entry<SkeletonData>>,
+    loads: u64,
+    hits: u64,
+}
+
+impl AssetCache {
+    pub fn new() -> Self {
+        AssetCache::default()
+    }
+
+    pub fn atlas(&mut self, path: &str) -> Result<Rc<Atlas>, Error> {
+        if let Some(entry) = self.atlases.get_mut(path) {
+            self.hits += 1;
+            return Ok(entry.hit());
+        }
+
+        self.load_atlas(path)
+    }
+
+    pub fn skeleton_data(
+        &mut self,
+        skeleton_path: &str,
+        atlas_path: &str,
+        scale: f32,
+    ) -> Result<Rc<SkeletonData>, Error> {
+        let key = SkeletonKey {
+            skeleton: skeleton_path.to_owned(),
+            atlas: atlas_path.to_owned(),
+            scale: scale.to_bits(),
+        };
+        if let Some(entry) = self.skeletons.get_mut(&key) {
+            self.hits += 1;
+            return Ok(entry.hit());
+        }
+
+        // not a hit, the atlas is only shared with the cached skeleton
+        let atlas = self.load_atlas(atlas_path)?;
+        let data = SkeletonJson::new(&atlas, scale)?
+            .read_skeleton_file(skeleton_path)?
+            .with_atlas(atlas);
+        let entry = Entry::new(data);
+        let data = Rc::clone(&entry.value);
+        self.skeletons.insert(key, entry);
+        self.loads += 1;
+
+        Ok(data)
+    }
+
+    pub fn atlas_usage(&self, path: &str) -> Option<Usage> {
+        // cached skeletons keep their atlas alive as well, those handles are not handed out
+        let cached = self
+            .skeletons
+            .keys()
+            .filter(|key| key.atlas == path)
+            .count();
+
+        self.atlases.get(path).map(|entry| {
+            let usage = entry.usage();
+            Usage {
+                handles: usage.handles.saturating_sub(cached),
+                ..usage
+            }
+        })
+    }
+
+    pub fn skeleton_usage(
+        &self,
+        skeleton_path: &str,
+        atlas_path: &str,
+        scale: f32,
+    ) -> Option<Usage> {
+        let key = SkeletonKey {
+            skeleton: skeleton_path.to_owned(),
+            atlas: atlas_path.to_owned(),
+            scale: scale.to_bits(),
+        };
+
+        self.skeletons.get(&key).map(Entry::usage)
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            atlases: self.atlases.len(),
+            skeletons: self.skeletons.len(),
+            loads: self.loads,
+            hits: self.hits,
+        }
+    }
+
+    // drops entries nobody holds a handle to, returns how many were evicted
+    pub fn evict_unused(&mut self) -> usize {
+        let before = self.atlases.len() + self.skeletons.len();
+
+        // skeletons first, they hold handles to their atlases
+        self.skeletons.retain(|_, entry| !entry.is_unused());
+        self.atlases.retain(|_, entry| !entry.is_unused());
+
+        before - self.atlases.len() - self.skeletons.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.skeletons.clear();
+        self.atlases.clear();
+    }
+
+    // the cached atlas or a new one, without counting a hit
+    fn load_atlas(&mut self, path: &str) -> Result<Rc<Atlas>, Error> {
+        if let Some(entry) = self.atlases.get(path) {
+            return Ok(Rc::clone(&entry.value));
+        }
+
+        let entry = Entry::new(Atlas::from_file(path)?);
+        let atlas = Rc::clone(&entry.value);
+        self.atlases.insert(path.to_owned(), entry);
+        self.loads += 1;
+
+        Ok(atlas)
+    }
+}
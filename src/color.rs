@@ -0,0 +1,38 @@
+use libspine_sys::spColor;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Color { r, g, b, a }
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Color::new(1., 1., 1., 1.)
+    }
+}
+
+impl From<spColor> for Color {
+    fn from(raw: spColor) -> Self {
+        Color::new(raw.r, raw.g, raw.b, raw.a)
+    }
+}
+
+impl From<Color> for spColor {
+    fn from(color: Color) -> Self {
+        spColor {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: color.a,
+        }
+    }
+}
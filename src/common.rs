@@ -1,6 +1,53 @@
+use std::ffi::CStr;
+use std::mem;
+use std::os::raw::{c_char, c_int, c_void};
+use std::slice;
+
 pub unsafe fn from_raw_buf<T: Copy>(ptr: *const T, elts: usize) -> Vec<T> {
-    let mut dst = Vec::with_capacity(elts);
-    dst.set_len(elts);
-    ptr.copy_to(dst.as_mut_ptr(), elts);
-    dst
+    slice::from_raw_parts(ptr, elts).to_vec()
+}
+
+pub unsafe fn from_raw_buf_or_empty<T: Copy>(ptr: *const T, count: i32) -> Vec<T> {
+    if ptr.is_null() || count <= 0 {
+        return Vec::new();
+    }
+
+    from_raw_buf(ptr, count as usize)
+}
+
+pub unsafe fn collect_from_raw<'a, R, T>(ptr: *const *mut R, count: i32) -> Vec<T>
+where
+    R: 'a,
+    T: From<&'a R>,
+{
+    from_raw_buf_or_empty(ptr, count)
+        .iter()
+        .filter_map(|p| p.as_ref().map(|raw_ref| T::from(raw_ref)))
+        .collect()
+}
+
+pub unsafe fn to_string(ptr: *const c_char) -> String {
+    to_opt_string(ptr).unwrap_or_default()
+}
+
+pub unsafe fn to_opt_string(ptr: *const c_char) -> Option<String> {
+    ptr.as_ref()
+        .map(|c_str| CStr::from_ptr(c_str).to_string_lossy().into_owned())
+}
+
+extern "C" {
+    // spine-c allocator (extension.h), for buffers that spine-c later frees itself
+    fn _calloc(num: usize, size: usize, file: *const c_char, line: c_int) -> *mut c_void;
+    fn _free(ptr: *mut c_void);
+}
+
+pub unsafe fn spine_calloc<T>(count: usize) -> *mut T {
+    let file = concat!(file!(), "\0");
+    _calloc(count, mem::size_of::<T>(), file.as_ptr() as *const c_char, line!() as c_int) as *mut T
+}
+
+pub unsafe fn spine_free<T>(ptr: *mut T) {
+    if !ptr.is_null() {
+        _free(ptr as *mut c_void);
+    }
 }
@@ -0,0 +1,134 @@
+use common::{from_raw_buf, to_string};
+use libspine_sys::*;
+
+pub type PositionMode = spPositionMode;
+pub type SpacingMode = spSpacingMode;
+pub type RotateMode = spRotateMode;
+
+#[derive(Debug, Clone)]
+pub struct IkConstraintData {
+    pub name: String,
+    pub order: i32,
+    pub bones: Vec<String>,
+    pub target: String,
+    pub bend_direction: i32,
+    pub mix: f32,
+}
+
+impl<'a> From<&'a spIkConstraintData> for IkConstraintData {
+    fn from(raw_ref: &'a spIkConstraintData) -> Self {
+        unsafe {
+            IkConstraintData {
+                name: to_string(raw_ref.name),
+                order: raw_ref.order,
+                bones: bone_names(raw_ref.bones, raw_ref.bonesCount),
+                target: bone_name(raw_ref.target),
+                bend_direction: raw_ref.bendDirection,
+                mix: raw_ref.mix,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransformConstraintData {
+    pub name: String,
+    pub order: i32,
+    pub bones: Vec<String>,
+    pub target: String,
+    pub rotate_mix: f32,
+    pub translate_mix: f32,
+    pub scale_mix: f32,
+    pub shear_mix: f32,
+    pub offset_rotation: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub offset_scale_x: f32,
+    pub offset_scale_y: f32,
+    pub offset_shear_y: f32,
+    pub relative: bool,
+    pub local: bool,
+}
+
+impl<'a> From<&'a spTransformConstraintData> for TransformConstraintData {
+    fn from(raw_ref: &'a spTransformConstraintData) -> Self {
+        unsafe {
+            TransformConstraintData {
+                name: to_string(raw_ref.name),
+                order: raw_ref.order,
+                bones: bone_names(raw_ref.bones, raw_ref.bonesCount),
+                target: bone_name(raw_ref.target),
+                rotate_mix: raw_ref.rotateMix,
+                translate_mix: raw_ref.translateMix,
+                scale_mix: raw_ref.scaleMix,
+                shear_mix: raw_ref.shearMix,
+                offset_rotation: raw_ref.offsetRotation,
+                offset_x: raw_ref.offsetX,
+                offset_y: raw_ref.offsetY,
+                offset_scale_x: raw_ref.offsetScaleX,
+                offset_scale_y: raw_ref.offsetScaleY,
+                offset_shear_y: raw_ref.offsetShearY,
+                relative: raw_ref.relative != 0,
+                local: raw_ref.local != 0,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PathConstraintData {
+    pub name: String,
+    pub order: i32,
+    pub bones: Vec<String>,
+    pub target: String,
+    pub position_mode: PositionMode,
+    pub spacing_mode: SpacingMode,
+    pub rotate_mode: RotateMode,
+    pub offset_rotation: f32,
+    pub position: f32,
+    pub spacing: f32,
+    pub rotate_mix: f32,
+    pub translate_mix: f32,
+}
+
+impl<'a> From<&'a spPathConstraintData> for PathConstraintData {
+    fn from(raw_ref: &'a spPathConstraintData) -> Self {
+        unsafe {
+            PathConstraintData {
+                name: to_string(raw_ref.name),
+                order: raw_ref.order,
+                bones: bone_names(raw_ref.bones, raw_ref.bonesCount),
+                target: raw_ref
+                    .target
+                    .as_ref()
+                    .map(|slot| to_string(slot.name))
+                    .unwrap_or_default(),
+                position_mode: raw_ref.positionMode,
+                spacing_mode: raw_ref.spacingMode,
+                rotate_mode: raw_ref.rotateMode,
+                offset_rotation: raw_ref.offsetRotation,
+                position: raw_ref.position,
+                spacing: raw_ref.spacing,
+                rotate_mix: raw_ref.rotateMix,
+                translate_mix: raw_ref.translateMix,
+            }
+        }
+    }
+}
+
+unsafe fn bone_name(bone: *const spBoneData) -> String {
+    bone.as_ref()
+        .map(|bone| to_string(bone.name))
+        .unwrap_or_default()
+}
+
+unsafe fn bone_names(bones: *mut *mut spBoneData, count: i32) -> Vec<String> {
+    if bones.is_null() || count <= 0 {
+        return Vec::new();
+    }
+
+    from_raw_buf(bones, count as usize)
+        .iter()
+        .map(|bone| bone_name(*bone))
+        .collect()
+}
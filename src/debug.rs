@@ -0,0 +1,217 @@
+use attachment::vertex::Vertex;
+use attachment::Attachment;
+use render::QUAD_TRIANGLES;
+use skeleton::Skeleton;
+
+pub const BONE_LINE_COLOR: [f32; 4] = [1., 0., 0., 1.];
+pub const BONE_ORIGIN_COLOR: [f32; 4] = [0., 1., 0., 1.];
+pub const REGION_COLOR: [f32; 4] = [0., 0., 1., 0.5];
+pub const MESH_HULL_COLOR: [f32; 4] = [0., 0., 1., 0.5];
+pub const MESH_TRIANGLE_COLOR: [f32; 4] = [1., 0.64, 0., 0.5];
+pub const BOUNDING_BOX_COLOR: [f32; 4] = [0., 1., 0., 1.];
+pub const PATH_COLOR: [f32; 4] = [1., 0.5, 0., 1.];
+pub const PATH_HANDLE_COLOR: [f32; 4] = [0.75, 0.75, 0.75, 1.];
+pub const CLIPPING_COLOR: [f32; 4] = [0.8, 0.8, 0.8, 1.];
+pub const POINT_COLOR: [f32; 4] = [1., 1., 0., 1.];
+
+const CURVE_SEGMENTS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line {
+    pub from: [f32; 2],
+    pub to: [f32; 2],
+    pub color: [f32; 4],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub position: [f32; 2],
+    pub color: [f32; 4],
+}
+
+// world space lines and points, colors are straight alpha
+#[derive(Debug, Clone, Default)]
+pub struct Geometry {
+    pub lines: Vec<Line>,
+    pub points: Vec<Point>,
+}
+
+impl Geometry {
+    fn line(&mut self, from: [f32; 2], to: [f32; 2], color: [f32; 4]) {
+        self.lines.push(Line { from, to, color });
+    }
+
+    fn polygon(&mut self, vertices: &[[f32; 2]], color: [f32; 4]) {
+        for (i, from) in vertices.iter().enumerate() {
+            self.line(*from, vertices[(i + 1) % vertices.len()], color);
+        }
+    }
+
+    fn triangles(&mut self, vertices: &[[f32; 2]], triangles: &[u16], color: [f32; 4]) {
+        for triangle in triangles.chunks(3).filter(|triangle| triangle.len() == 3) {
+            let corners: Vec<[f32; 2]> = triangle
+                .iter()
+                .filter_map(|index| vertices.get(*index as usize).cloned())
+                .collect();
+            if corners.len() == 3 {
+                self.polygon(&corners, color);
+            }
+        }
+    }
+
+    fn curve(&mut self, points: [[f32; 2]; 4], color: [f32; 4]) {
+        let mut from = points[0];
+        for segment in 1..CURVE_SEGMENTS + 1 {
+            let to = bezier(&points, segment as f32 / CURVE_SEGMENTS as f32);
+            self.line(from, to, color);
+            from = to;
+        }
+
+        self.line(points[0], points[1], PATH_HANDLE_COLOR);
+        self.line(points[3], points[2], PATH_HANDLE_COLOR);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub bones: bool,
+    pub bone_origins: bool,
+    pub regions: bool,
+    pub mesh_hulls: bool,
+    pub mesh_triangles: bool,
+    pub bounding_boxes: bool,
+    pub paths: bool,
+    pub clipping: bool,
+    pub points: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            bones: true,
+            bone_origins: true,
+            regions: true,
+            mesh_hulls: true,
+            mesh_triangles: true,
+            bounding_boxes: true,
+            paths: true,
+            clipping: true,
+            points: true,
+        }
+    }
+}
+
+// the skeleton has to be posed, geometry is taken from the current world transform
+pub fn geometry(skeleton: &Skeleton, options: &Options) -> Geometry {
+    let mut geometry = Geometry::default();
+
+    for slot in skeleton.slots_ordered().iter() {
+        let (attachment, bone) = match (slot.attachment(), slot.bone()) {
+            (Some(attachment), Some(bone)) => (attachment, bone),
+            _ => continue,
+        };
+
+        match attachment {
+            Attachment::Region(ref region) if options.regions => {
+                let vertices = region.world_vertices(&bone);
+                geometry.polygon(&vertices, REGION_COLOR);
+                if options.mesh_triangles {
+                    geometry.triangles(&vertices, &QUAD_TRIANGLES, MESH_TRIANGLE_COLOR);
+                }
+            }
+            Attachment::Mesh(ref mesh) | Attachment::LinkedMesh(ref mesh) => {
+                let vertices = mesh.world_vertices(slot);
+                if options.mesh_triangles {
+                    geometry.triangles(&vertices, &mesh.triangles(), MESH_TRIANGLE_COLOR);
+                }
+                if options.mesh_hulls {
+                    let hull_len = (mesh.hull_length() as usize / 2).min(vertices.len());
+                    geometry.polygon(&vertices[..hull_len], MESH_HULL_COLOR);
+                }
+            }
+            Attachment::BoundingBox(ref bounding_box) if options.bounding_boxes => {
+                geometry.polygon(&bounding_box.world_vertices(slot), BOUNDING_BOX_COLOR);
+            }
+            Attachment::Clipping(ref clipping) if options.clipping => {
+                geometry.polygon(&clipping.world_vertices(slot), CLIPPING_COLOR);
+            }
+            Attachment::Path(ref path) if options.paths => {
+                path_curves(&mut geometry, &path.world_vertices(slot), path.closed());
+            }
+            Attachment::Point(ref point) if options.points => {
+                let (x, y) = point.compute_world_position(&bone);
+                let rotation = point.compute_world_rotation(&bone).to_radians();
+                let length = 20.;
+                geometry.line(
+                    [x, y],
+                    [x + rotation.cos() * length, y + rotation.sin() * length],
+                    POINT_COLOR,
+                );
+                geometry.points.push(Point {
+                    position: [x, y],
+                    color: POINT_COLOR,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    for bone in skeleton.bones().iter() {
+        let origin = [bone.world_x(), bone.world_y()];
+        let length = bone.length();
+        if options.bones && length > 0. {
+            let (a, _, c, _) = bone.world_matrix();
+            let end = [origin[0] + a * length, origin[1] + c * length];
+            geometry.line(origin, end, BONE_LINE_COLOR);
+        }
+        if options.bone_origins {
+            geometry.points.push(Point {
+                position: origin,
+                color: BONE_ORIGIN_COLOR,
+            });
+        }
+    }
+
+    geometry
+}
+
+// path vertices are laid out as in-handle, point, out-handle for every point
+fn path_curves(geometry: &mut Geometry, vertices: &[[f32; 2]], closed: bool) {
+    if vertices.len() < 4 {
+        return;
+    }
+
+    let last = vertices.len() - 1;
+    if closed {
+        geometry.curve(
+            [vertices[1], vertices[0], vertices[last], vertices[last - 1]],
+            PATH_COLOR,
+        );
+    }
+
+    let mut start = 1;
+    while start + 3 < vertices.len() {
+        geometry.curve(
+            [
+                vertices[start],
+                vertices[start + 1],
+                vertices[start + 2],
+                vertices[start + 3],
+            ],
+            PATH_COLOR,
+        );
+        start += 3;
+    }
+}
+
+fn bezier(points: &[[f32; 2]; 4], t: f32) -> [f32; 2] {
+    let u = 1. - t;
+    let weights = [u * u * u, 3. * u * u * t, 3. * u * t * t, t * t * t];
+    let mut out = [0.; 2];
+    for (point, weight) in points.iter().zip(weights.iter()) {
+        out[0] += point[0] * weight;
+        out[1] += point[1] * weight;
+    }
+
+    out
+}
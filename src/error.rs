@@ -0,0 +1,104 @@
+use std::error::Error as StdError;
+use std::ffi::NulError;
+use std::fmt;
+use std::io;
+use std::result;
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    NullPointer { what: &'static str },
+    Parse { file: String, message: String },
+    AnimationNotFound(String),
+    InvalidTrackIndex(i32),
+    SkinNotFound(String),
+    BoneNotFound(String),
+    RegionNotFound(String),
+    SnapshotMismatch,
+    DataMismatch,
+    AtlasNotOwned,
+    InvalidVertexRange { start: usize, count: usize, len: usize },
+    InvalidStride(usize),
+    VertexBufferTooSmall { required: usize, len: usize },
+    VertexOffsetOverflow { offset: usize, stride: usize },
+    DeformTooShort { required: usize, len: usize },
+    CanvasTooLarge { width: u32, height: u32 },
+    InvalidCString(NulError),
+    Io(io::Error),
+    TextureLoad { path: String, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NullPointer { what } => write!(f, "spine-c returned null for {}", what),
+            Error::Parse {
+                ref file,
+                ref message,
+            } => write!(f, "failed to parse {}: {}", file, message),
+            Error::AnimationNotFound(ref name) => write!(f, "animation not found: {}", name),
+            Error::InvalidTrackIndex(index) => write!(f, "invalid track index: {}", index),
+            Error::SkinNotFound(ref name) => write!(f, "skin not found: {}", name),
+            Error::BoneNotFound(ref name) => write!(f, "bone not found: {}", name),
+            Error::RegionNotFound(ref name) => write!(f, "region not found: {}", name),
+            Error::SnapshotMismatch => write!(f, "snapshot was taken from different data"),
+            Error::DataMismatch => write!(f, "skeleton and state use different skeleton data"),
+            Error::AtlasNotOwned => write!(f, "skeleton data does not keep its atlas alive"),
+            Error::InvalidVertexRange { start, count, len } => write!(
+                f,
+                "vertex range {}..{} is out of bounds for {} world vertices",
+                start,
+                start.saturating_add(count),
+                len
+            ),
+            Error::InvalidStride(stride) => write!(f, "invalid vertex stride: {}", stride),
+            Error::VertexBufferTooSmall { required, len } => write!(
+                f,
+                "vertex buffer holds {} floats, {} are required",
+                len, required
+            ),
+            Error::VertexOffsetOverflow { offset, stride } => write!(
+                f,
+                "vertex offset {} with stride {} is out of the range spine-c can index",
+                offset, stride
+            ),
+            Error::DeformTooShort { required, len } => write!(
+                f,
+                "slot deform holds {} floats of another attachment, {} are read",
+                len, required
+            ),
+            Error::CanvasTooLarge { width, height } => {
+                write!(f, "a {}x{} canvas does not fit in memory", width, height)
+            }
+            Error::InvalidCString(ref err) => write!(f, "invalid C string: {}", err),
+            Error::Io(ref err) => write!(f, "io error: {}", err),
+            Error::TextureLoad {
+                ref path,
+                ref message,
+            } => write!(f, "failed to load texture {}: {}", path, message),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            Error::InvalidCString(ref err) => Some(err),
+            Error::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<NulError> for Error {
+    fn from(err: NulError) -> Self {
+        Error::InvalidCString(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
@@ -0,0 +1,51 @@
+use common::{to_opt_string, to_string};
+use libspine_sys::{spEvent, spEventData};
+
+#[derive(Debug, Clone)]
+pub struct EventData {
+    pub name: String,
+    pub int_value: i32,
+    pub float_value: f32,
+    pub string_value: Option<String>,
+}
+
+impl<'a> From<&'a spEventData> for EventData {
+    fn from(raw_ref: &'a spEventData) -> Self {
+        unsafe {
+            EventData {
+                name: to_string(raw_ref.name),
+                int_value: raw_ref.intValue,
+                float_value: raw_ref.floatValue,
+                string_value: to_opt_string(raw_ref.stringValue),
+            }
+        }
+    }
+}
+
+// a fired event, its values may differ from the defaults in `EventData`
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub name: String,
+    pub time: f32,
+    pub int_value: i32,
+    pub float_value: f32,
+    pub string_value: Option<String>,
+}
+
+impl<'a> From<&'a spEvent> for Event {
+    fn from(raw_ref: &'a spEvent) -> Self {
+        unsafe {
+            Event {
+                name: raw_ref
+                    .data
+                    .as_ref()
+                    .map(|data| to_string(data.name))
+                    .unwrap_or_default(),
+                time: raw_ref.time,
+                int_value: raw_ref.intValue,
+                float_value: raw_ref.floatValue,
+                string_value: to_opt_string(raw_ref.stringValue),
+            }
+        }
+    }
+}
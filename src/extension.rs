@@ -1,8 +1,111 @@
-#[derive(Debug, Clone)]
+use atlas::page::{Format, Sampler};
+use error::Error;
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+thread_local! {
+    static PANIC: RefCell<Option<Box<dyn Any + Send>>> = RefCell::new(None);
+}
+
+// Unwinding through spine-c is undefined behavior, so every hook it calls runs user code
+// through here. The panic is kept until spine-c returns and `resume_panic` rethrows it;
+// `None` tells the hook to fail the way spine-c expects.
+#[doc(hidden)]
+pub fn catch_panic<R, F: FnOnce() -> R>(f: F) -> Option<R> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            PANIC.with(|stash| {
+                // the first panic is the interesting one, later ones come from cleanup
+                stash.borrow_mut().get_or_insert(payload);
+            });
+            None
+        }
+    }
+}
+
+// a panic caught while already unwinding is dropped, resuming it would abort
+pub(crate) fn resume_panic() {
+    if let Some(payload) = PANIC.with(|stash| stash.borrow_mut().take()) {
+        if !thread::panicking() {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+// Handed to the texture loader with `format` and `sampler` already filled from the atlas
+// page, the loader sets `buffer`, `width` and `height`. spine 3.6 atlases carry no `pma`
+// setting, so `premultiplied_alpha` is up to the loader.
+#[derive(Debug, Clone, Default)]
 pub struct Texture {
     pub buffer: Vec<u8>,
     pub width: u32,
     pub height: u32,
+    pub format: Format,
+    pub sampler: Sampler,
+    pub premultiplied_alpha: bool,
+}
+
+impl Texture {
+    pub fn premultiply_alpha(&mut self) {
+        if !self.premultiplied_alpha {
+            premultiply_alpha(&mut self.buffer);
+            self.premultiplied_alpha = true;
+        }
+    }
+}
+
+// The texture hook of `extend_spine!` comes in two forms: `fn(&str) -> Result<Texture, E>`
+// returns a new texture, `format` and `sampler` are then filled from the page afterwards;
+// `fn(&str, &mut Texture) -> Result<(), E>` fills a texture that carries them already.
+#[doc(hidden)]
+pub trait TextureHook<Form> {
+    fn load(&self, path: &str, texture: &mut Texture) -> Result<(), Error>;
+}
+
+#[doc(hidden)]
+pub struct ReturnsTexture;
+
+#[doc(hidden)]
+pub struct FillsTexture;
+
+impl<F, E> TextureHook<ReturnsTexture> for F
+where
+    F: Fn(&str) -> Result<Texture, E>,
+    Error: From<E>,
+{
+    fn load(&self, path: &str, texture: &mut Texture) -> Result<(), Error> {
+        let loaded = self(path)?;
+        *texture = Texture {
+            format: texture.format,
+            sampler: texture.sampler,
+            ..loaded
+        };
+
+        Ok(())
+    }
+}
+
+impl<F, E> TextureHook<FillsTexture> for F
+where
+    F: Fn(&str, &mut Texture) -> Result<(), E>,
+    Error: From<E>,
+{
+    fn load(&self, path: &str, texture: &mut Texture) -> Result<(), Error> {
+        Ok(self(path, texture)?)
+    }
+}
+
+// converts a straight alpha RGBA buffer in place
+pub fn premultiply_alpha(buffer: &mut [u8]) {
+    for pixel in buffer.chunks_mut(4).filter(|pixel| pixel.len() == 4) {
+        let alpha = u32::from(pixel[3]);
+        for channel in pixel[..3].iter_mut() {
+            *channel = ((u32::from(*channel) * alpha + 127) / 255) as u8;
+        }
+    }
 }
 
 #[macro_export]
@@ -11,20 +114,22 @@ macro_rules! extend_spine {
         pub mod extend_spine {
             use std::ffi::{CString, CStr};
             use std::os::raw::{c_char, c_int, c_void};
-            use std::io::{Error, ErrorKind};
-            use std::error::Error as StdErr;
             use libc;
             use libspine_sys::spAtlasPage;
             use super::{$read_file, $read_texture};
             use spiner::extension::Texture;
+            use spiner::Error;
 
             impl_read_file!($read_file);
             impl_create_texture!($read_texture);
 
             #[no_mangle]
-            pub extern fn _spAtlasPage_disposeTexture(atlas: *mut spAtlasPage) {
+            pub unsafe extern fn _spAtlasPage_disposeTexture(atlas: *mut spAtlasPage) {
                 unsafe {
-                    Box::from_raw((*atlas).rendererObject as *mut Texture);
+                    let texture = (*atlas).rendererObject as *mut Texture;
+                    if !texture.is_null() {
+                        drop(Box::from_raw(texture));
+                    }
                 }
             }
         }
@@ -35,16 +140,18 @@ macro_rules! extend_spine {
 macro_rules! impl_read_file {
     ($r:ident) => {
         #[no_mangle]
-        pub extern fn _spUtil_readFile(path: *const c_char, length: *mut c_int) -> *const c_char {
-            let read = || path_str!(path)
-                .and_then($r)
-                .and_then(|buf| CString::new(buf).map_err(Error::from));
-            let c_string = match read() {
-                Ok(s) => s,
-                Err(err) => {
+        pub unsafe extern fn _spUtil_readFile(path: *const c_char, length: *mut c_int) -> *const c_char {
+            let read = || -> Result<CString, Error> {
+                let buf = $r(path_str!(path)?)?;
+                Ok(CString::new(buf)?)
+            };
+            let c_string = match $crate::extension::catch_panic(read) {
+                Some(Ok(s)) => s,
+                Some(Err(err)) => {
                     eprintln!("Error: {}", err);
                     return 0 as *const c_char;
                 }
+                None => return 0 as *const c_char,
             };
             let len = c_string.to_bytes().len();
 
@@ -60,12 +167,31 @@ macro_rules! impl_read_file {
 macro_rules! impl_create_texture {
     ($r:ident) => {
         #[no_mangle]
-        pub extern fn _spAtlasPage_createTexture(atlas: *mut spAtlasPage, path: *const c_char) {
-            let texture = Box::new(path_str!(path).and_then($r).unwrap());
+        pub unsafe extern fn _spAtlasPage_createTexture(atlas: *mut spAtlasPage, path: *const c_char) {
+            // the page settings are parsed before the texture is requested
+            let mut texture = Box::new(unsafe {
+                Texture {
+                    format: (*atlas).format.into(),
+                    sampler: $crate::atlas::page::Sampler::from_raw(&*atlas),
+                    ..Default::default()
+                }
+            });
+            let read = |texture: &mut Texture| -> Result<(), Error> {
+                $crate::extension::TextureHook::load(&$r, path_str!(path)?, texture)
+            };
+            // a page left without renderer object is reported by `Atlas::check_textures`
+            match $crate::extension::catch_panic(|| read(&mut texture)) {
+                Some(Ok(())) => {}
+                Some(Err(err)) => {
+                    eprintln!("Error: {}", err);
+                    return;
+                }
+                None => return,
+            }
             let width = texture.width;
             let height = texture.height;
             let ptr = Box::into_raw(texture) as *mut _ as *mut c_void;
-            
+
             unsafe {
                 (*atlas).width = width as i32;
                 (*atlas).height = height as i32;
@@ -80,7 +206,9 @@ macro_rules! path_str {
     ($n:ident) => {
         (unsafe { CStr::from_ptr($n) })
             .to_str()
-            .map_err(|err| Error::new(ErrorKind::Other, err.description()))
+            .map_err(|err| {
+                $crate::Error::from(::std::io::Error::new(::std::io::ErrorKind::InvalidData, err))
+            })
     }
 }
 
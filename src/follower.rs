@@ -0,0 +1,83 @@
+use error::Error;
+use libspine_sys::spBoneData;
+use math::Affine;
+use raw::*;
+use skeleton::Skeleton;
+use std::ptr;
+
+// World transform of a followed bone. spine-c 3.6 folds the skeleton position and flip into
+// the bone world transform, skeletons have no scale of their own (it is applied when loading).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Follow {
+    pub x: f32,
+    pub y: f32,
+    // degrees, counterclockwise from the world x axis
+    pub rotation: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub transform: Affine,
+}
+
+// Keeps a game object on a bone, e.g. a weapon sprite on a hand. Query it every frame after
+// `Skeleton::update_world_transform`.
+#[derive(Debug, Clone)]
+pub struct BoneFollower {
+    pub position_only: bool,
+    bone_index: usize,
+    // tells skeletons of the same data apart from others that happen to have the index
+    bone_data: *const spBoneData,
+}
+
+impl BoneFollower {
+    pub fn new(skeleton: &Skeleton, bone: &str) -> Result<Self, Error> {
+        let bone = skeleton
+            .find_bone(bone)
+            .ok_or_else(|| Error::BoneNotFound(bone.to_owned()))?;
+        if bone.index() < 0 {
+            return Err(Error::NullPointer {
+                what: "spBone.data",
+            });
+        }
+
+        Ok(BoneFollower {
+            position_only: false,
+            bone_index: bone.index() as usize,
+            bone_data: bone.as_raw().data,
+        })
+    }
+
+    // `None` if the skeleton was created from other data, reloaded data included
+    pub fn follow(&self, skeleton: &Skeleton) -> Option<Follow> {
+        let bone = skeleton.bone(self.bone_index)?;
+        if !ptr::eq(bone.as_raw().data, self.bone_data) {
+            return None;
+        }
+        let (x, y) = (bone.world_x(), bone.world_y());
+
+        if self.position_only {
+            return Some(Follow {
+                x,
+                y,
+                rotation: 0.,
+                scale_x: 1.,
+                scale_y: 1.,
+                transform: Affine::translation(x, y),
+            });
+        }
+
+        let transform = bone.world_transform();
+        let scale_x = transform.a.hypot(transform.c);
+        let det = transform.a * transform.d - transform.b * transform.c;
+        // a mirrored bone keeps its rotation and reports the reflection as a negative y scale
+        let scale_y = if det < 0. { -1. } else { 1. } * transform.b.hypot(transform.d);
+
+        Some(Follow {
+            x,
+            y,
+            rotation: transform.c.atan2(transform.a).to_degrees(),
+            scale_x,
+            scale_y,
+            transform,
+        })
+    }
+}
@@ -3,10 +3,23 @@ extern crate libspine_sys;
 #[macro_use]
 mod raw;
 mod common;
+pub mod error;
 pub mod extension;
+pub mod color;
 pub mod bone;
 pub mod atlas;
 pub mod skeleton;
 pub mod slot;
+pub mod skin;
+pub mod constraint;
+pub mod event;
 pub mod attachment;
 pub mod animation;
+pub mod math;
+pub mod render;
+pub mod debug;
+pub mod cache;
+pub mod reload;
+pub mod follower;
+
+pub use error::{Error, Result};
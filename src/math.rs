@@ -0,0 +1,68 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Affine {
+    pub fn new(a: f32, b: f32, c: f32, d: f32, x: f32, y: f32) -> Self {
+        Affine { a, b, c, d, x, y }
+    }
+
+    pub fn identity() -> Self {
+        Affine::new(1., 0., 0., 1., 0., 0.)
+    }
+
+    pub fn translation(x: f32, y: f32) -> Self {
+        Affine::new(1., 0., 0., 1., x, y)
+    }
+
+    pub fn scale(x: f32, y: f32) -> Self {
+        Affine::new(x, 0., 0., y, 0., 0.)
+    }
+
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.b * y + self.x,
+            self.c * x + self.d * y + self.y,
+        )
+    }
+
+    pub fn then(&self, other: &Affine) -> Affine {
+        Affine {
+            a: other.a * self.a + other.b * self.c,
+            b: other.a * self.b + other.b * self.d,
+            c: other.c * self.a + other.d * self.c,
+            d: other.c * self.b + other.d * self.d,
+            x: other.a * self.x + other.b * self.y + other.x,
+            y: other.c * self.x + other.d * self.y + other.y,
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Affine> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0. {
+            return None;
+        }
+
+        let inv = 1. / det;
+        Some(Affine {
+            a: self.d * inv,
+            b: -self.b * inv,
+            c: -self.c * inv,
+            d: self.a * inv,
+            x: (self.b * self.y - self.d * self.x) * inv,
+            y: (self.c * self.x - self.a * self.y) * inv,
+        })
+    }
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Affine::identity()
+    }
+}
@@ -1,6 +1,9 @@
-pub use std::io::{Error, ErrorKind};
+pub use error::Error;
 pub use std::ptr::NonNull;
 
+/// # Safety
+///
+/// `as_raw` must return the live spine-c object the implementor wraps.
 pub unsafe trait AsRaw {
     type Raw;
     fn as_raw(&self) -> &Self::Raw;
@@ -16,15 +19,18 @@ where
     }
 }
 
+/// # Safety
+///
+/// `as_raw_mut` must point to the same object `as_raw` returns.
 pub unsafe trait AsRawMut: AsRaw {
     fn as_raw_mut(&mut self) -> *mut <Self as AsRaw>::Raw;
 }
 
 macro_rules! try_wrap {
-    ($ptr:ident, $cb:expr) => {
+    ($ptr:ident, $what:expr, $cb:expr) => {
         match NonNull::new($ptr) {
             Some(raw) => Ok($cb(raw)),
-            None => Err(Error::new(ErrorKind::Other, "Nul"))
+            None => Err(Error::NullPointer { what: $what })
         }
     }
 }
@@ -0,0 +1,238 @@
+use animation::state::{State, StateData};
+use atlas::Atlas;
+use common::to_opt_string;
+use error::Error;
+use raw::*;
+use skeleton::data::Data as SkeletonData;
+use skeleton::json::Json as SkeletonJson;
+use skeleton::Skeleton;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+// Polls modification times, so it works on every platform and filesystem without a
+// notification api. Files that cannot be read count as changed once they reappear.
+#[derive(Debug, Clone, Default)]
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let path = path.into();
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        Watcher { files }
+    }
+
+    // the skeleton, the atlas and every page image the atlas references
+    pub fn for_assets(skeleton_path: &str, atlas_path: &str, atlas: &Atlas) -> Self {
+        let dir = Path::new(atlas_path)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let pages = atlas.pages().map(|page| dir.join(&page.name));
+
+        Watcher::new(
+            vec![PathBuf::from(skeleton_path), PathBuf::from(atlas_path)]
+                .into_iter()
+                .chain(pages),
+        )
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    // true if any file changed since the last call, every stamp is refreshed
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+        for &mut (ref path, ref mut stamp) in self.files.iter_mut() {
+            let modified = modified(path);
+            if modified != *stamp {
+                *stamp = modified;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+// Keeps skeleton data in sync with its files on disk. Live skeletons and states are moved
+// over to new data with `rebind_skeleton` and `rebind_state`. Rebind both to the same data,
+// `State::apply` refuses a skeleton built from other data with `Error::DataMismatch`.
+pub struct HotReload {
+    skeleton_path: String,
+    atlas_path: String,
+    scale: f32,
+    data: Rc<SkeletonData>,
+    watcher: Watcher,
+    // a change was seen that no reload picked up yet
+    dirty: bool,
+}
+
+impl HotReload {
+    pub fn load(skeleton_path: &str, atlas_path: &str, scale: f32) -> Result<Self, Error> {
+        let (data, watcher) = load(skeleton_path, atlas_path, scale)?;
+
+        Ok(HotReload {
+            skeleton_path: skeleton_path.to_owned(),
+            atlas_path: atlas_path.to_owned(),
+            scale,
+            data,
+            watcher,
+            dirty: false,
+        })
+    }
+
+    pub fn data(&self) -> Rc<SkeletonData> {
+        Rc::clone(&self.data)
+    }
+
+    pub fn watcher(&self) -> &Watcher {
+        &self.watcher
+    }
+
+    // Returns the new data if anything changed. A failed reload keeps the previous data and
+    // is retried on every poll until it succeeds, exporters tend to write files in several
+    // steps.
+    pub fn poll(&mut self) -> Result<Option<Rc<SkeletonData>>, Error> {
+        if self.watcher.changed() {
+            self.dirty = true;
+        }
+        if !self.dirty {
+            return Ok(None);
+        }
+
+        self.reload().map(Some)
+    }
+
+    pub fn reload(&mut self) -> Result<Rc<SkeletonData>, Error> {
+        let (data, watcher) = load(&self.skeleton_path, &self.atlas_path, self.scale)?;
+        // pages may have been added or renamed
+        self.watcher = watcher;
+        self.data = data;
+        self.dirty = false;
+
+        Ok(self.data())
+    }
+}
+
+fn load(
+    skeleton_path: &str,
+    atlas_path: &str,
+    scale: f32,
+) -> Result<(Rc<SkeletonData>, Watcher), Error> {
+    let atlas = Atlas::from_file(atlas_path)?;
+    let watcher = Watcher::for_assets(skeleton_path, atlas_path, &atlas);
+    let data = SkeletonJson::new(&atlas, scale)?
+        .read_skeleton_file(skeleton_path)?
+        .with_atlas(Rc::new(atlas));
+
+    Ok((Rc::new(data), watcher))
+}
+
+// Replaces the skeleton with one created from `data`, keeping its skin, position, flip and
+// color. A skin that no longer exists falls back to the default skin.
+pub fn rebind_skeleton(skeleton: &mut Skeleton, data: Rc<SkeletonData>) -> Result<(), Error> {
+    let mut rebound = Skeleton::from_data(data)?;
+    let skin = unsafe {
+        skeleton
+            .as_raw()
+            .skin
+            .as_ref()
+            .and_then(|skin| to_opt_string(skin.name))
+    };
+
+    if let Some(skin) = skin {
+        if rebound.set_skin(&skin).is_ok() {
+            rebound.set_slots_to_setup_pose();
+        }
+    }
+
+    let old = skeleton.as_raw();
+    unsafe {
+        let new = &mut *rebound.as_raw_mut();
+        new.x = old.x;
+        new.y = old.y;
+        new.flipX = old.flipX;
+        new.flipY = old.flipY;
+        new.color = old.color;
+        new.time = old.time;
+    }
+
+    rebound.update_world_transform();
+    *skeleton = rebound;
+
+    Ok(())
+}
+
+// Replaces the state with one created from `data`. Current and queued animations are set
+// again by name with their track time, loop, time scale, alpha and blend; animations that no
+// longer exist are dropped. Mixes in progress restart from the current entry, the default mix,
+// the state time scale and undrained events carry over.
+pub fn rebind_state(state: &mut State, data: Rc<SkeletonData>) -> Result<(), Error> {
+    let mut state_data = StateData::from_skeleton_data(data)?;
+    state_data.set_default_mix(unsafe { (*state.as_raw().data).defaultMix });
+
+    let mut rebound = State::from_data(Rc::new(state_data))?;
+    rebound.set_time_scale(state.time_scale());
+
+    for current in state.tracks() {
+        let track_index = current.track_index();
+        let mut entry = Some(current);
+        let mut first = true;
+
+        while let Some(old) = entry {
+            entry = old.next();
+            let name = match old.animation() {
+                Some(animation) => animation.name,
+                None => continue,
+            };
+
+            let result = if first {
+                rebound.set_animation_by_name(track_index, &name, old.is_loop())
+            } else {
+                rebound.add_animation_by_name(track_index, &name, old.is_loop(), old.delay())
+            };
+            let mut new = match result {
+                Ok(new) => new,
+                Err(Error::AnimationNotFound(_)) => continue,
+                Err(err) => return Err(err),
+            };
+
+            first = false;
+            new.set_delay(old.delay());
+            new.set_track_time(old.track_time());
+            new.set_track_end(old.track_end());
+            new.set_time_scale(old.time_scale());
+            new.set_alpha(old.alpha());
+            new.set_mix_blend(old.mix_blend());
+            new.set_mix_duration(old.mix_duration());
+        }
+    }
+
+    // starts raised while rebuilding are not reported, pending notifications are kept
+    rebound.take_events(state);
+    *state = rebound;
+
+    Ok(())
+}
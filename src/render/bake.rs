@@ -0,0 +1,242 @@
+use super::{Batch, Batcher, BlendFunc, Bounds, Vertex};
+use animation::state::{State, StateData};
+use atlas::page::Page;
+use raw::*;
+use skeleton::data::Data as SkeletonData;
+use skeleton::Skeleton;
+use slot::BlendMode;
+use std::mem;
+use std::ptr;
+use std::rc::Rc;
+
+// a batch of one baked frame, indices are relative to the batch's first vertex
+#[derive(Debug, Clone, Copy)]
+pub struct BakedBatch {
+    // index into `BakedAnimation::pages`
+    pub page: Option<usize>,
+    pub blend_mode: BlendMode,
+    pub premultiplied_alpha: bool,
+    vertex_start: usize,
+    vertex_count: usize,
+    index_start: usize,
+    index_count: usize,
+}
+
+impl BakedBatch {
+    pub fn blend_func(&self) -> BlendFunc {
+        BlendFunc::new(self.blend_mode, self.premultiplied_alpha)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BakedFrame {
+    pub batches: Vec<BakedBatch>,
+    pub bounds: Bounds,
+}
+
+// Samples animations at a fixed rate into batched geometry, so many instances can be drawn
+// without posing a skeleton each.
+pub struct Baker {
+    pub fps: f32,
+    pub skin: Option<String>,
+    batcher: Batcher,
+}
+
+impl Baker {
+    pub fn new(fps: f32) -> Self {
+        Baker {
+            fps,
+            skin: None,
+            batcher: Batcher::new(),
+        }
+    }
+
+    pub fn bake(
+        &mut self,
+        data: Rc<SkeletonData>,
+        animation: &str,
+    ) -> Result<BakedAnimation, Error> {
+        // the baked pages are kept as raw handles, only the data can keep their atlas alive
+        if !data.owns_atlas() {
+            return Err(Error::AtlasNotOwned);
+        }
+
+        let mut skeleton = Skeleton::from_data(Rc::clone(&data))?;
+        if let Some(ref skin) = self.skin {
+            skeleton.set_skin(skin)?;
+        }
+        let mut state =
+            State::from_data(Rc::new(StateData::from_skeleton_data(Rc::clone(&data))?))?;
+        let entry = state.set_animation_by_name(0, animation, false)?;
+        let duration = entry.animation().map_or(0., |animation| animation.duration);
+
+        let fps = self.fps.max(1.);
+        // the last frame holds the pose at the very end, looping playback skips it
+        let cycle_frames = ((duration * fps).ceil() as usize).max(1);
+        let mut baked = BakedAnimation {
+            name: animation.to_owned(),
+            duration,
+            fps,
+            pages: Vec::new(),
+            frames: Vec::with_capacity(cycle_frames + 1),
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            data,
+        };
+
+        for frame in 0..cycle_frames + 1 {
+            let time = (frame as f32 / fps).min(duration);
+            if let Some(mut entry) = state.current(0) {
+                entry.set_track_time(time);
+            }
+            skeleton.set_to_setup_pose();
+            state.apply(&mut skeleton)?;
+            state.drain_events().for_each(drop);
+            skeleton.update_world_transform();
+
+            let batches = self.batcher.batch(&skeleton);
+            baked.push_frame(batches);
+        }
+
+        baked.vertices.shrink_to_fit();
+        baked.indices.shrink_to_fit();
+
+        Ok(baked)
+    }
+}
+
+// Geometry of every baked frame in skeleton space, served without calling into spine-c.
+// Keeps the skeleton data alive and with it the atlas of the pages, `Baker::bake` only takes
+// data that owns its atlas, as loaded by `AssetCache`, `HotReload` or `Json::lenient`.
+pub struct BakedAnimation {
+    pub name: String,
+    pub duration: f32,
+    pub fps: f32,
+    pages: Vec<Page>,
+    frames: Vec<BakedFrame>,
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+    data: Rc<SkeletonData>,
+}
+
+impl BakedAnimation {
+    pub fn data(&self) -> Rc<SkeletonData> {
+        Rc::clone(&self.data)
+    }
+
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    pub fn frames(&self) -> &[BakedFrame] {
+        &self.frames
+    }
+
+    // the frame shown at `time`, wrapping around when looping and holding the end otherwise
+    pub fn frame(&self, time: f32, loop_: bool) -> &BakedFrame {
+        let cycle_frames = self.frames.len() - 1;
+        let time = time.max(0.);
+        let index = if loop_ {
+            // wrapped by the duration first, the last frame of a cycle may be cut short
+            if self.duration > 0. {
+                (((time % self.duration) * self.fps) as usize).min(cycle_frames - 1)
+            } else {
+                0
+            }
+        } else if time >= self.duration {
+            cycle_frames
+        } else {
+            ((time * self.fps) as usize).min(cycle_frames)
+        };
+
+        &self.frames[index]
+    }
+
+    pub fn vertices(&self, batch: &BakedBatch) -> &[Vertex] {
+        &self.vertices[batch.vertex_start..batch.vertex_start + batch.vertex_count]
+    }
+
+    pub fn indices(&self, batch: &BakedBatch) -> &[u32] {
+        &self.indices[batch.index_start..batch.index_start + batch.index_count]
+    }
+
+    // copies the frame into `Batch`es at the given position, for the existing renderers
+    pub fn batches(&self, time: f32, loop_: bool, x: f32, y: f32) -> Vec<Batch> {
+        self.frame(time, loop_)
+            .batches
+            .iter()
+            .map(|batch| Batch {
+                page: batch
+                    .page
+                    .map(|index| Page::from_raw(NonNull::from(self.pages[index].as_raw()))),
+                blend_mode: batch.blend_mode,
+                premultiplied_alpha: batch.premultiplied_alpha,
+                vertices: self
+                    .vertices(batch)
+                    .iter()
+                    .map(|vertex| Vertex {
+                        position: [vertex.position[0] + x, vertex.position[1] + y],
+                        ..*vertex
+                    })
+                    .collect(),
+                indices: self.indices(batch).to_vec(),
+            })
+            .collect()
+    }
+
+    // bytes held by the baked geometry, the skeleton data and atlas are not included
+    pub fn memory_size(&self) -> usize {
+        let batches: usize = self
+            .frames
+            .iter()
+            .map(|frame| frame.batches.capacity() * mem::size_of::<BakedBatch>())
+            .sum();
+
+        mem::size_of::<BakedAnimation>()
+            + self.name.capacity()
+            + self.pages.capacity() * mem::size_of::<Page>()
+            + self.frames.capacity() * mem::size_of::<BakedFrame>()
+            + batches
+            + self.vertices.capacity() * mem::size_of::<Vertex>()
+            + self.indices.capacity() * mem::size_of::<u32>()
+    }
+
+    fn push_frame(&mut self, batches: Vec<Batch>) {
+        let bounds = Bounds::from_batches(&batches);
+        let batches = batches
+            .into_iter()
+            .map(|batch| {
+                let baked = BakedBatch {
+                    page: batch.page.map(|page| self.page_index(page)),
+                    blend_mode: batch.blend_mode,
+                    premultiplied_alpha: batch.premultiplied_alpha,
+                    vertex_start: self.vertices.len(),
+                    vertex_count: batch.vertices.len(),
+                    index_start: self.indices.len(),
+                    index_count: batch.indices.len(),
+                };
+                self.vertices.extend_from_slice(&batch.vertices);
+                self.indices.extend_from_slice(&batch.indices);
+
+                baked
+            })
+            .collect();
+
+        self.frames.push(BakedFrame { batches, bounds });
+    }
+
+    fn page_index(&mut self, page: Page) -> usize {
+        let raw = page.as_raw() as *const _;
+        match self
+            .pages
+            .iter()
+            .position(|known| ptr::eq(known.as_raw(), raw))
+        {
+            Some(index) => index,
+            None => {
+                self.pages.push(page);
+                self.pages.len() - 1
+            }
+        }
+    }
+}
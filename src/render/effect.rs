@@ -0,0 +1,112 @@
+use super::Vertex;
+use raw::*;
+use skeleton::Skeleton;
+use std::f32::consts::PI;
+
+// Per-vertex deformation applied by `Batcher::batch_with_effect`. `begin` and `end` wrap
+// every batched skeleton. spine-c 3.6 has no `spVertexEffect`, the effects below follow
+// the ones shipped with later spine-c versions.
+pub trait VertexEffect {
+    fn begin(&mut self, _skeleton: &Skeleton) {}
+    fn transform(&mut self, vertex: &mut Vertex);
+    fn end(&mut self) {}
+}
+
+#[derive(Debug, Clone)]
+pub struct JitterEffect {
+    pub jitter_x: f32,
+    pub jitter_y: f32,
+    seed: u32,
+}
+
+impl JitterEffect {
+    pub fn new(jitter_x: f32, jitter_y: f32) -> Self {
+        JitterEffect::with_seed(jitter_x, jitter_y, 0x9e37_79b9)
+    }
+
+    pub fn with_seed(jitter_x: f32, jitter_y: f32, seed: u32) -> Self {
+        JitterEffect {
+            jitter_x,
+            jitter_y,
+            // xorshift never leaves zero
+            seed: seed.max(1),
+        }
+    }
+
+    fn random(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+
+        (self.seed >> 8) as f32 / (1 << 24) as f32
+    }
+
+    // triangular distribution in -range..range, centered on zero
+    fn random_triangular(&mut self, range: f32) -> f32 {
+        (self.random() - self.random()) * range
+    }
+}
+
+impl VertexEffect for JitterEffect {
+    fn transform(&mut self, vertex: &mut Vertex) {
+        let (jitter_x, jitter_y) = (self.jitter_x, self.jitter_y);
+        vertex.position[0] += self.random_triangular(jitter_x);
+        vertex.position[1] += self.random_triangular(jitter_y);
+    }
+}
+
+// rotates vertices within `radius` of the center, by up to `angle` degrees at the center
+#[derive(Debug, Clone)]
+pub struct SwirlEffect {
+    pub center_x: f32,
+    pub center_y: f32,
+    pub radius: f32,
+    pub angle: f32,
+    world_x: f32,
+    world_y: f32,
+}
+
+impl SwirlEffect {
+    pub fn new(radius: f32) -> Self {
+        SwirlEffect {
+            center_x: 0.,
+            center_y: 0.,
+            radius,
+            angle: 0.,
+            world_x: 0.,
+            world_y: 0.,
+        }
+    }
+}
+
+impl VertexEffect for SwirlEffect {
+    // the center is relative to the skeleton position
+    fn begin(&mut self, skeleton: &Skeleton) {
+        let raw = skeleton.as_raw();
+        self.world_x = raw.x + self.center_x;
+        self.world_y = raw.y + self.center_y;
+    }
+
+    fn transform(&mut self, vertex: &mut Vertex) {
+        let x = vertex.position[0] - self.world_x;
+        let y = vertex.position[1] - self.world_y;
+        let dist = (x * x + y * y).sqrt();
+        if dist >= self.radius {
+            return;
+        }
+
+        let theta = self.angle * PI / 180. * pow2((self.radius - dist) / self.radius);
+        let (sine, cosine) = theta.sin_cos();
+        vertex.position[0] = cosine * x - sine * y + self.world_x;
+        vertex.position[1] = sine * x + cosine * y + self.world_y;
+    }
+}
+
+// ease in-out, as spine's `Interpolation.pow2`
+fn pow2(a: f32) -> f32 {
+    if a <= 0.5 {
+        (a * 2.).powi(2) / 2.
+    } else {
+        ((a - 1.) * 2.).powi(2) / -2. + 1.
+    }
+}
@@ -0,0 +1,266 @@
+use self::effect::VertexEffect;
+use atlas::page::Page;
+use attachment::vertex::Vertex as VertexAttachment;
+use attachment::Attachment;
+use color::Color;
+use libspine_sys::spAtlasPage;
+use libspine_sys::spBlendMode::*;
+use raw::*;
+use skeleton::Skeleton;
+use slot::BlendMode;
+use std::ptr;
+
+pub mod bake;
+pub mod effect;
+pub mod software;
+
+pub(crate) const QUAD_TRIANGLES: [u16; 6] = [0, 1, 2, 2, 3, 0];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vertex {
+    pub position: [f32; 2],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstColor,
+}
+
+// source and destination factors for an additive blend equation,
+// as the official runtimes set them up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlendFunc {
+    pub src: BlendFactor,
+    pub dst: BlendFactor,
+}
+
+impl BlendFunc {
+    pub fn new(blend_mode: BlendMode, premultiplied_alpha: bool) -> Self {
+        let src_alpha = if premultiplied_alpha {
+            BlendFactor::One
+        } else {
+            BlendFactor::SrcAlpha
+        };
+        let (src, dst) = match blend_mode {
+            SP_BLEND_MODE_NORMAL => (src_alpha, BlendFactor::OneMinusSrcAlpha),
+            SP_BLEND_MODE_ADDITIVE => (src_alpha, BlendFactor::One),
+            SP_BLEND_MODE_MULTIPLY => (BlendFactor::DstColor, BlendFactor::OneMinusSrcAlpha),
+            SP_BLEND_MODE_SCREEN => (BlendFactor::One, BlendFactor::OneMinusSrcColor),
+        };
+
+        BlendFunc { src, dst }
+    }
+}
+
+// vertex colors of a batch on a premultiplied alpha page are premultiplied as well
+pub struct Batch {
+    pub page: Option<Page>,
+    pub blend_mode: BlendMode,
+    pub premultiplied_alpha: bool,
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+}
+
+impl Batch {
+    pub fn blend_func(&self) -> BlendFunc {
+        BlendFunc::new(self.blend_mode, self.premultiplied_alpha)
+    }
+
+    fn accepts(&self, page: &Option<Page>, blend_mode: BlendMode) -> bool {
+        let page_ptr = |page: &Option<Page>| {
+            page.as_ref()
+                .map_or(ptr::null(), |page| page.as_raw() as *const spAtlasPage)
+        };
+
+        self.blend_mode == blend_mode && page_ptr(&self.page) == page_ptr(page)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+impl Bounds {
+    pub fn empty() -> Self {
+        Bounds {
+            min_x: f32::INFINITY,
+            min_y: f32::INFINITY,
+            max_x: f32::NEG_INFINITY,
+            max_y: f32::NEG_INFINITY,
+        }
+    }
+
+    pub fn from_batches(batches: &[Batch]) -> Self {
+        let mut bounds = Bounds::empty();
+        for vertex in batches.iter().flat_map(|batch| batch.vertices.iter()) {
+            bounds.include(vertex.position[0], vertex.position[1]);
+        }
+
+        bounds
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min_x > self.max_x || self.min_y > self.max_y
+    }
+
+    pub fn include(&mut self, x: f32, y: f32) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    pub fn merge(&mut self, other: &Bounds) {
+        if !other.is_empty() {
+            self.include(other.min_x, other.min_y);
+            self.include(other.max_x, other.max_y);
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        (self.max_x - self.min_x).max(0.)
+    }
+
+    pub fn height(&self) -> f32 {
+        (self.max_y - self.min_y).max(0.)
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        ((self.min_x + self.max_x) / 2., (self.min_y + self.max_y) / 2.)
+    }
+}
+
+#[derive(Default)]
+pub struct Batcher {
+    world_vertices: Vec<f32>,
+}
+
+impl Batcher {
+    pub fn new() -> Self {
+        Batcher::default()
+    }
+
+    pub fn batch(&mut self, skeleton: &Skeleton) -> Vec<Batch> {
+        self.batch_skeleton(skeleton, None)
+    }
+
+    pub fn batch_with_effect(
+        &mut self,
+        skeleton: &Skeleton,
+        effect: &mut dyn VertexEffect,
+    ) -> Vec<Batch> {
+        effect.begin(skeleton);
+        let batches = self.batch_skeleton(skeleton, Some(&mut *effect));
+        effect.end();
+
+        batches
+    }
+
+    fn batch_skeleton(
+        &mut self,
+        skeleton: &Skeleton,
+        mut effect: Option<&mut dyn VertexEffect>,
+    ) -> Vec<Batch> {
+        let mut batches: Vec<Batch> = Vec::new();
+        let skeleton_color = Color::from(skeleton.as_raw().color);
+
+        for slot in skeleton.slots_ordered().iter() {
+            let attachment = match slot.attachment() {
+                None => continue,
+                Some(attach) => attach,
+            };
+
+            let (page, attachment_color, uvs, triangles) = match attachment {
+                Attachment::Region(ref region) => {
+                    let bone = match slot.bone() {
+                        None => continue,
+                        Some(bone) => bone,
+                    };
+                    region.compute_world_vertices_vec(&bone, &mut self.world_vertices);
+
+                    (
+                        region.atlas_region().and_then(|region| region.page()),
+                        Color::from(region.as_raw().color),
+                        region.uvs().to_vec(),
+                        QUAD_TRIANGLES.to_vec(),
+                    )
+                }
+                Attachment::Mesh(ref mesh) | Attachment::LinkedMesh(ref mesh) => {
+                    mesh.compute_world_vertices_vec(slot, &mut self.world_vertices);
+
+                    (
+                        mesh.atlas_region().and_then(|region| region.page()),
+                        Color::from(mesh.as_raw().color),
+                        mesh.uvs(),
+                        mesh.triangles(),
+                    )
+                }
+                _ => continue,
+            };
+
+            let slot_raw = slot.as_raw();
+            let slot_color = Color::from(slot_raw.color);
+            let blend_mode = unsafe { (*slot_raw.data).blendMode };
+            let premultiplied_alpha = page
+                .as_ref()
+                .and_then(|page| page.renderer_object())
+                .map(|texture| texture.premultiplied_alpha)
+                .unwrap_or(false);
+            let alpha = skeleton_color.a * slot_color.a * attachment_color.a;
+            let rgb_scale = if premultiplied_alpha { alpha } else { 1. };
+            let color = [
+                skeleton_color.r * slot_color.r * attachment_color.r * rgb_scale,
+                skeleton_color.g * slot_color.g * attachment_color.g * rgb_scale,
+                skeleton_color.b * slot_color.b * attachment_color.b * rgb_scale,
+                alpha,
+            ];
+
+            let needs_batch = match batches.last() {
+                Some(batch) => !batch.accepts(&page, blend_mode),
+                None => true,
+            };
+            if needs_batch {
+                batches.push(Batch {
+                    page,
+                    blend_mode,
+                    premultiplied_alpha,
+                    vertices: Vec::new(),
+                    indices: Vec::new(),
+                });
+            }
+
+            let batch = batches.last_mut().unwrap();
+            let base = batch.vertices.len() as u32;
+            let vertices_count = uvs.len() / 2;
+
+            for i in 0..vertices_count {
+                let mut vertex = Vertex {
+                    position: [self.world_vertices[i * 2], self.world_vertices[i * 2 + 1]],
+                    uv: [uvs[i * 2], uvs[i * 2 + 1]],
+                    color,
+                };
+                if let Some(ref mut effect) = effect {
+                    effect.transform(&mut vertex);
+                }
+                batch.vertices.push(vertex);
+            }
+            batch
+                .indices
+                .extend(triangles.iter().map(|index| base + *index as u32));
+        }
+
+        batches
+    }
+}
@@ -0,0 +1,344 @@
+//! Rasterizes the `Batcher` output on the CPU, for thumbnails, tests and headless tools.
+//!
+//! Not supported: two color tinting, the dark color of slots is ignored and only the light
+//! color tints; clipping attachments, clipped slots are drawn whole. Both are skipped by
+//! `Batcher` already, so the other renderers built on it share these limits.
+
+use super::effect::VertexEffect;
+use super::{Batch, Batcher, Bounds, Vertex};
+use atlas::page::{Filter, Wrap};
+use debug::Geometry;
+use error::Error;
+use extension::Texture;
+use libspine_sys::spBlendMode::*;
+use math::Affine;
+use skeleton::Skeleton;
+use slot::BlendMode;
+
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32) -> Result<Self, Error> {
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|len| len.checked_mul(4))
+            .ok_or(Error::CanvasTooLarge { width, height })?;
+
+        Ok(Canvas {
+            width,
+            height,
+            pixels: vec![0; len],
+        })
+    }
+
+    pub fn clear(&mut self, color: [u8; 4]) {
+        for pixel in self.pixels.chunks_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let offset = self.offset(x, y);
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[offset..offset + 4]);
+
+        Some(pixel)
+    }
+
+    // in usize, `new` checked that the whole buffer fits
+    fn offset(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    // `src` is premultiplied, the canvas itself keeps straight alpha
+    fn blend(&mut self, x: u32, y: u32, src: [f32; 4], blend_mode: BlendMode) {
+        let offset = self.offset(x, y);
+        let dst = &mut self.pixels[offset..offset + 4];
+
+        let sa = src[3];
+        let da = f32::from(dst[3]) / 255.;
+        let out_a = sa + da * (1. - sa);
+        if out_a <= 0. {
+            return;
+        }
+
+        for (channel, &s) in dst[..3].iter_mut().zip(src.iter()) {
+            let d = f32::from(*channel) / 255. * da;
+            let out = match blend_mode {
+                SP_BLEND_MODE_NORMAL => s + d * (1. - sa),
+                SP_BLEND_MODE_ADDITIVE => s + d,
+                SP_BLEND_MODE_MULTIPLY => s * d + d * (1. - sa),
+                SP_BLEND_MODE_SCREEN => s + d * (1. - s),
+            };
+            *channel = to_u8(out / out_a);
+        }
+        dst[3] = to_u8(out_a);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub view: Affine,
+}
+
+impl Camera {
+    // maps world units (y up) to canvas pixels (y down), `center` ends up in the middle
+    pub fn new(center: (f32, f32), zoom: f32, width: u32, height: u32) -> Self {
+        Camera {
+            view: Affine::new(
+                zoom,
+                0.,
+                0.,
+                -zoom,
+                width as f32 / 2. - center.0 * zoom,
+                height as f32 / 2. + center.1 * zoom,
+            ),
+        }
+    }
+
+    // largest zoom that keeps `bounds` inside the canvas with `padding` pixels on every side
+    pub fn fit(bounds: &Bounds, width: u32, height: u32, padding: f32) -> Self {
+        if bounds.is_empty() {
+            return Camera::new((0., 0.), 1., width, height);
+        }
+
+        let zoom_x = (width as f32 - padding * 2.).max(1.) / bounds.width().max(1.);
+        let zoom_y = (height as f32 - padding * 2.).max(1.) / bounds.height().max(1.);
+
+        Camera::new(bounds.center(), zoom_x.min(zoom_y), width, height)
+    }
+}
+
+#[derive(Default)]
+pub struct Renderer {
+    batcher: Batcher,
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Renderer::default()
+    }
+
+    pub fn render(&mut self, skeleton: &Skeleton, camera: &Camera, canvas: &mut Canvas) {
+        for batch in self.batcher.batch(skeleton).iter() {
+            draw_batch(batch, camera, canvas);
+        }
+    }
+
+    pub fn render_with_effect(
+        &mut self,
+        skeleton: &Skeleton,
+        effect: &mut dyn VertexEffect,
+        camera: &Camera,
+        canvas: &mut Canvas,
+    ) {
+        for batch in self.batcher.batch_with_effect(skeleton, effect).iter() {
+            draw_batch(batch, camera, canvas);
+        }
+    }
+}
+
+pub fn draw_batch(batch: &Batch, camera: &Camera, canvas: &mut Canvas) {
+    let texture = batch.page.as_ref().and_then(|page| page.renderer_object());
+
+    draw_triangles(
+        &batch.vertices,
+        &batch.indices,
+        texture,
+        batch.blend_mode,
+        camera,
+        canvas,
+    );
+}
+
+pub fn draw_triangles(
+    vertices: &[Vertex],
+    indices: &[u32],
+    texture: Option<&Texture>,
+    blend_mode: BlendMode,
+    camera: &Camera,
+    canvas: &mut Canvas,
+) {
+    for triangle in indices.chunks(3).filter(|triangle| triangle.len() == 3) {
+        let mut corners = [vertices[triangle[0] as usize]; 3];
+        for (corner, index) in corners.iter_mut().zip(triangle.iter()) {
+            *corner = vertices[*index as usize];
+            let (x, y) = camera.view.apply(corner.position[0], corner.position[1]);
+            corner.position = [x, y];
+        }
+
+        draw_triangle(&corners, texture, blend_mode, canvas);
+    }
+}
+
+// lines are one pixel wide, points are drawn as small squares
+pub fn draw_debug(geometry: &Geometry, camera: &Camera, canvas: &mut Canvas) {
+    for line in geometry.lines.iter() {
+        let from = camera.view.apply(line.from[0], line.from[1]);
+        let to = camera.view.apply(line.to[0], line.to[1]);
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil().max(1.) as usize;
+
+        for step in 0..steps + 1 {
+            let t = step as f32 / steps as f32;
+            let x = from.0 + (to.0 - from.0) * t;
+            let y = from.1 + (to.1 - from.1) * t;
+            plot(canvas, x, y, line.color);
+        }
+    }
+
+    for point in geometry.points.iter() {
+        let (x, y) = camera.view.apply(point.position[0], point.position[1]);
+        for dy in -1..2 {
+            for dx in -1..2 {
+                plot(canvas, x + dx as f32, y + dy as f32, point.color);
+            }
+        }
+    }
+}
+
+fn plot(canvas: &mut Canvas, x: f32, y: f32, color: [f32; 4]) {
+    if x < 0. || y < 0. || x >= canvas.width as f32 || y >= canvas.height as f32 {
+        return;
+    }
+
+    let src = [color[0] * color[3], color[1] * color[3], color[2] * color[3], color[3]];
+    canvas.blend(x as u32, y as u32, src, SP_BLEND_MODE_NORMAL);
+}
+
+fn draw_triangle(
+    corners: &[Vertex; 3],
+    texture: Option<&Texture>,
+    blend_mode: BlendMode,
+    canvas: &mut Canvas,
+) {
+    let [p0, p1, p2] = [corners[0].position, corners[1].position, corners[2].position];
+    let premultiplied_alpha = texture
+        .map(|texture| texture.premultiplied_alpha)
+        .unwrap_or(false);
+    let area = edge(p0, p1, p2);
+    if area == 0. || canvas.width == 0 || canvas.height == 0 {
+        return;
+    }
+
+    let min_x = p0[0].min(p1[0]).min(p2[0]).floor().max(0.) as u32;
+    let min_y = p0[1].min(p1[1]).min(p2[1]).floor().max(0.) as u32;
+    let max_x = (p0[0].max(p1[0]).max(p2[0]).ceil().max(0.) as u32).min(canvas.width);
+    let max_y = (p0[1].max(p1[1]).max(p2[1]).ceil().max(0.) as u32).min(canvas.height);
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = [x as f32 + 0.5, y as f32 + 0.5];
+            let weights = [edge(p1, p2, p) / area, edge(p2, p0, p) / area, edge(p0, p1, p) / area];
+            if weights.iter().any(|w| *w < 0.) {
+                continue;
+            }
+
+            let mut uv = [0.; 2];
+            let mut color = [0.; 4];
+            for (corner, weight) in corners.iter().zip(weights.iter()) {
+                for (uv, corner_uv) in uv.iter_mut().zip(corner.uv.iter()) {
+                    *uv += corner_uv * weight;
+                }
+                for (color, corner_color) in color.iter_mut().zip(corner.color.iter()) {
+                    *color += corner_color * weight;
+                }
+            }
+
+            let texel = texture.map_or([1.; 4], |texture| sample(texture, uv));
+            let mut src = [
+                texel[0] * color[0],
+                texel[1] * color[1],
+                texel[2] * color[2],
+                texel[3] * color[3],
+            ];
+            if !premultiplied_alpha {
+                let alpha = src[3];
+                for channel in src[..3].iter_mut() {
+                    *channel *= alpha;
+                }
+            }
+            if src[3] > 0. {
+                canvas.blend(x, y, src, blend_mode);
+            }
+        }
+    }
+}
+
+fn edge(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+// bilinear unless the page asks for nearest magnification, wrapped as the page specifies
+fn sample(texture: &Texture, uv: [f32; 2]) -> [f32; 4] {
+    if texture.width == 0 || texture.height == 0 {
+        return [0.; 4];
+    }
+
+    let sampler = texture.sampler;
+    let x = uv[0] * texture.width as f32 - 0.5;
+    let y = uv[1] * texture.height as f32 - 0.5;
+
+    let texel = |tx: f32, ty: f32| {
+        let tx = wrap(tx as i64, texture.width, sampler.u_wrap);
+        let ty = wrap(ty as i64, texture.height, sampler.v_wrap);
+        let offset = ((ty * texture.width + tx) * 4) as usize;
+        let mut out = [0.; 4];
+        if let Some(pixel) = texture.buffer.get(offset..offset + 4) {
+            for (channel, value) in out.iter_mut().zip(pixel.iter()) {
+                *channel = f32::from(*value) / 255.;
+            }
+        }
+        out
+    };
+
+    if sampler.mag_filter == Filter::Nearest {
+        return texel(x.round(), y.round());
+    }
+
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (t00, t10) = (texel(x0, y0), texel(x0 + 1., y0));
+    let (t01, t11) = (texel(x0, y0 + 1.), texel(x0 + 1., y0 + 1.));
+
+    lerp(&lerp(&t00, &t10, fx), &lerp(&t01, &t11, fx), fy)
+}
+
+fn lerp(from: &[f32; 4], to: &[f32; 4], t: f32) -> [f32; 4] {
+    let mut out = [0.; 4];
+    for ((out, from), to) in out.iter_mut().zip(from.iter()).zip(to.iter()) {
+        *out = from + (to - from) * t;
+    }
+
+    out
+}
+
+fn wrap(coord: i64, size: u32, mode: Wrap) -> u32 {
+    let size = i64::from(size);
+    let wrapped = match mode {
+        Wrap::ClampToEdge => coord.max(0).min(size - 1),
+        Wrap::Repeat => ((coord % size) + size) % size,
+        Wrap::MirroredRepeat => {
+            let period = ((coord % (size * 2)) + size * 2) % (size * 2);
+            if period < size {
+                period
+            } else {
+                size * 2 - 1 - period
+            }
+        }
+    };
+
+    wrapped as u32
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0., 1.) * 255. + 0.5) as u8
+}
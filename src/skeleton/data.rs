@@ -1,19 +1,52 @@
-use super::json::Json;
 use animation::Animation;
-use common::from_raw_buf;
+use atlas::Atlas;
+use attachment::mesh::Mesh;
+use attachment::loader::Loader;
+use bone::BoneData;
+use common::{collect_from_raw, from_raw_buf, from_raw_buf_or_empty, to_opt_string, to_string};
+use constraint::{IkConstraintData, PathConstraintData, TransformConstraintData};
+use event::EventData;
+use extension::resume_panic;
 use libspine_sys::*;
 use raw::*;
+use skin::Skin;
+use slot::SlotData;
 use std::ffi::CString;
+use std::os::raw::c_char;
 use std::ptr::NonNull;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct Metadata {
+    pub version: Option<String>,
+    pub hash: Option<String>,
+    pub width: f32,
+    pub height: f32,
+}
 
 pub struct Data {
     raw: NonNull<spSkeletonData>,
+    // dropped after the skeleton data, its attachments call back into the loader
+    loader: Option<Loader>,
+    // attachments point into the atlas regions
+    atlas: Option<Rc<Atlas>>,
 }
 
 impl_as_raw!(Data, raw, spSkeletonData);
 impl_as_raw_mut!(Data, raw);
 
 impl Data {
+    pub fn metadata(&self) -> Metadata {
+        let raw = self.as_raw();
+
+        Metadata {
+            version: unsafe { to_opt_string(raw.version) },
+            hash: unsafe { to_opt_string(raw.hash) },
+            width: raw.width,
+            height: raw.height,
+        }
+    }
+
     pub fn animations(&self) -> Vec<Animation> {
         let raw = self.as_raw();
         let animations_count = raw.animationsCount as usize;
@@ -21,24 +54,170 @@ impl Data {
 
         animations_raw
             .iter()
-            .filter_map(|p| unsafe { p.as_ref().map(|anim_ref| Animation::from(anim_ref)) })
+            .filter_map(|p| unsafe { p.as_ref().map(Animation::from) })
             .collect()
     }
 
     pub fn find_animation_by_name(&self, name: &str) -> Option<Animation> {
-        let raw_ptr = unsafe {
-            let c_str = CString::new(name).unwrap();
-            spSkeletonData_findAnimation(
-                self.as_raw() as *const _ as *mut spSkeletonData,
-                c_str.as_ptr(),
-            )
-        };
+        self.find(name, |raw, c_name| unsafe { spSkeletonData_findAnimation(raw, c_name) })
+            .map(Animation::from)
+    }
+
+    pub fn bones(&self) -> Vec<BoneData> {
+        let raw = self.as_raw();
+
+        unsafe { collect_from_raw(raw.bones, raw.bonesCount) }
+    }
+
+    pub fn find_bone(&self, name: &str) -> Option<BoneData> {
+        self.find(name, |raw, c_name| unsafe { spSkeletonData_findBone(raw, c_name) })
+            .map(BoneData::from)
+    }
+
+    pub fn slots(&self) -> Vec<SlotData> {
+        let raw = self.as_raw();
+
+        unsafe { collect_from_raw(raw.slots, raw.slotsCount) }
+    }
 
-        unsafe { raw_ptr.as_ref().map(|anim_ref| Animation::from(anim_ref)) }
+    pub fn find_slot(&self, name: &str) -> Option<SlotData> {
+        self.find(name, |raw, c_name| unsafe { spSkeletonData_findSlot(raw, c_name) })
+            .map(SlotData::from)
+    }
+
+    pub fn skins(&self) -> Vec<Skin> {
+        let raw = self.as_raw();
+        let skins_count = raw.skinsCount as usize;
+        let skins_raw = unsafe { from_raw_buf(raw.skins, skins_count) };
+
+        skins_raw
+            .iter()
+            .filter_map(|p| unsafe { p.as_ref().map(|skin_ref| self.skin_from_raw(skin_ref)) })
+            .collect()
+    }
+
+    pub fn default_skin(&self) -> Option<Skin> {
+        unsafe {
+            self.as_raw()
+                .defaultSkin
+                .as_ref()
+                .map(|skin_ref| self.skin_from_raw(skin_ref))
+        }
+    }
+
+    // spine-c 3.6 does not keep the skin of a linked mesh, it is the skin its parent was found in
+    pub fn linked_mesh_skin(&self, mesh: &Mesh) -> Option<String> {
+        let parent = mesh.parent_mesh()?.as_raw() as *const _ as *mut spAttachment;
+        let raw = self.as_raw();
+        let skins_raw = unsafe { from_raw_buf_or_empty(raw.skins, raw.skinsCount) };
+
+        skins_raw
+            .iter()
+            .filter_map(|p| unsafe { p.as_ref() })
+            .find(|skin_ref| {
+                self.skin_from_raw(skin_ref).entries.iter().any(|entry| {
+                    let c_name = match CString::new(entry.name.as_str()) {
+                        Ok(c_name) => c_name,
+                        Err(_) => return false,
+                    };
+                    let ptr = unsafe {
+                        spSkin_getAttachment(*skin_ref, entry.slot_index, c_name.as_ptr())
+                    };
+                    ptr == parent
+                })
+            })
+            .map(|skin_ref| unsafe { to_string(skin_ref.name) })
+    }
+
+    pub fn find_skin(&self, name: &str) -> Option<Skin> {
+        self.find(name, |raw, c_name| unsafe { spSkeletonData_findSkin(raw, c_name) })
+            .map(|skin_ref| self.skin_from_raw(skin_ref))
+    }
+
+    pub fn events(&self) -> Vec<EventData> {
+        let raw = self.as_raw();
+
+        unsafe { collect_from_raw(raw.events, raw.eventsCount) }
+    }
+
+    pub fn find_event(&self, name: &str) -> Option<EventData> {
+        self.find(name, |raw, c_name| unsafe { spSkeletonData_findEvent(raw, c_name) })
+            .map(EventData::from)
+    }
+
+    pub fn ik_constraints(&self) -> Vec<IkConstraintData> {
+        let raw = self.as_raw();
+
+        unsafe { collect_from_raw(raw.ikConstraints, raw.ikConstraintsCount) }
+    }
+
+    pub fn find_ik_constraint(&self, name: &str) -> Option<IkConstraintData> {
+        self.find(name, |raw, c_name| unsafe {
+            spSkeletonData_findIkConstraint(raw, c_name)
+        }).map(IkConstraintData::from)
+    }
+
+    pub fn transform_constraints(&self) -> Vec<TransformConstraintData> {
+        let raw = self.as_raw();
+
+        unsafe { collect_from_raw(raw.transformConstraints, raw.transformConstraintsCount) }
+    }
+
+    pub fn find_transform_constraint(&self, name: &str) -> Option<TransformConstraintData> {
+        self.find(name, |raw, c_name| unsafe {
+            spSkeletonData_findTransformConstraint(raw, c_name)
+        }).map(TransformConstraintData::from)
+    }
+
+    pub fn path_constraints(&self) -> Vec<PathConstraintData> {
+        let raw = self.as_raw();
+
+        unsafe { collect_from_raw(raw.pathConstraints, raw.pathConstraintsCount) }
+    }
+
+    pub fn find_path_constraint(&self, name: &str) -> Option<PathConstraintData> {
+        self.find(name, |raw, c_name| unsafe {
+            spSkeletonData_findPathConstraint(raw, c_name)
+        }).map(PathConstraintData::from)
     }
 
     pub fn from_raw(raw: NonNull<spSkeletonData>) -> Self {
-        Data { raw }
+        Data {
+            raw,
+            loader: None,
+            atlas: None,
+        }
+    }
+
+    pub(crate) fn with_loader(mut self, loader: Option<Loader>) -> Self {
+        self.loader = loader;
+        self
+    }
+
+    pub(crate) fn with_atlas(mut self, atlas: Rc<Atlas>) -> Self {
+        self.atlas = Some(atlas);
+        self
+    }
+
+    pub(crate) fn owns_atlas(&self) -> bool {
+        self.atlas.is_some()
+    }
+
+    fn skin_from_raw(&self, skin_ref: &spSkin) -> Skin {
+        Skin::from_raw(skin_ref, self.as_raw().slotsCount)
+    }
+
+    fn find<R, F>(&self, name: &str, find: F) -> Option<&R>
+    where
+        F: FnOnce(*mut spSkeletonData, *const c_char) -> *mut R,
+    {
+        let c_name = CString::new(name).ok()?;
+        let ptr = find(
+            self.as_raw() as *const _ as *mut spSkeletonData,
+            c_name.as_ptr(),
+        );
+
+        unsafe { ptr.as_ref() }
     }
 }
 
@@ -47,5 +226,7 @@ impl Drop for Data {
         unsafe {
             spSkeletonData_dispose(self.raw.as_ptr());
         }
+        // raised by the loader's `dispose`
+        resume_panic();
     }
 }
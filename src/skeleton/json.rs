@@ -1,70 +1,136 @@
 use super::data::Data as SkeletonData;
 use atlas::Atlas;
+use attachment::atlas_loader::{AtlasLoader, MissingRegion};
+use attachment::loader::{AttachmentLoader, Loader};
+use common::to_opt_string;
+use extension::resume_panic;
 use libspine_sys::*;
 use raw::*;
-use std::error::Error as ErrorTrait;
-use std::ffi::CStr;
+use std::cell::RefCell;
 use std::ffi::CString;
-use std::ffi::NulError;
+use std::rc::Rc;
 
-pub struct Json {
-    raw: NonNull<spSkeletonJson>,
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub missing_regions: Vec<MissingRegion>,
 }
 
-impl_as_raw!(Json, raw, spSkeletonJson);
-impl_as_raw_mut!(Json, raw);
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.missing_regions.is_empty()
+    }
 
-#[derive(Debug)]
-pub struct JsonError(String);
+    // distinct region paths, in the order they were first referenced
+    pub fn missing_region_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for missing in self.missing_regions.iter() {
+            if !names.contains(&missing.path) {
+                names.push(missing.path.clone());
+            }
+        }
 
-impl From<NulError> for JsonError {
-    fn from(err: NulError) -> Self {
-        JsonError(err.description().to_string())
+        names
     }
 }
 
-impl From<Error> for JsonError {
-    fn from(err: Error) -> Self {
-        JsonError(err.description().to_string())
-    }
+pub struct Json {
+    raw: NonNull<spSkeletonJson>,
+    loader: Option<Loader>,
+    atlas: Option<Rc<Atlas>>,
+    missing_regions: Option<Rc<RefCell<Vec<MissingRegion>>>>,
 }
 
-impl From<JsonError> for Error {
-    fn from(json: JsonError) -> Self {
-        Error::new(ErrorKind::Other, json.0)
-    }
-}
+impl_as_raw!(Json, raw, spSkeletonJson);
+impl_as_raw_mut!(Json, raw);
 
 impl Json {
-    pub fn new(atlas: &Atlas, scale: f32) -> Result<Json, JsonError> {
+    pub fn new(atlas: &Atlas, scale: f32) -> Result<Json, Error> {
         let ptr = unsafe { spSkeletonJson_create(atlas.as_raw() as *const _ as *mut spAtlas) };
 
-        let mut raw = try_wrap!(ptr, |raw| raw)?;
+        let mut raw = try_wrap!(ptr, "spSkeletonJson", |raw| raw)?;
         unsafe { raw.as_mut().scale = scale };
 
-        Ok(Json { raw })
+        Ok(Json {
+            raw,
+            loader: None,
+            atlas: None,
+            missing_regions: None,
+        })
     }
 
-    pub fn error(&self) -> Option<JsonError> {
-        let error = unsafe {
-            self.as_raw()
-                .error
-                .as_ref()
-                .map(|err| CStr::from_ptr(err).to_string_lossy().into_owned())
-        };
+    pub fn with_loader<L>(loader: L, scale: f32) -> Result<Json, Error>
+    where
+        L: AttachmentLoader + 'static,
+    {
+        let mut loader = Loader::new(loader)?;
+        // the json reader does not own the loader, the skeleton data keeps it alive instead
+        let ptr = unsafe { spSkeletonJson_createWithLoader(loader.as_raw_mut()) };
 
-        error.map(|err| JsonError(err))
+        let mut raw = try_wrap!(ptr, "spSkeletonJson", |raw| raw)?;
+        unsafe { raw.as_mut().scale = scale };
+
+        Ok(Json {
+            raw,
+            loader: Some(loader),
+            atlas: None,
+            missing_regions: None,
+        })
+    }
+
+    // missing regions are replaced by a placeholder instead of failing,
+    // see `read_skeleton_file_with_report`
+    pub fn lenient(atlas: Rc<Atlas>, scale: f32) -> Result<Json, Error> {
+        let loader = AtlasLoader::lenient(Rc::clone(&atlas))?;
+        let missing_regions = loader.missing_regions();
+
+        let mut json = Json::with_loader(loader, scale)?;
+        json.atlas = Some(atlas);
+        json.missing_regions = Some(missing_regions);
+
+        Ok(json)
+    }
+
+    pub fn error(&self) -> Option<String> {
+        unsafe { to_opt_string(self.as_raw().error) }
     }
 
-    pub fn read_skeleton_file(mut self, path: &str) -> Result<SkeletonData, JsonError> {
+    pub fn read_skeleton_file(mut self, path: &str) -> Result<SkeletonData, Error> {
         let c_path = CString::new(path)?;
         let ptr =
             unsafe { spSkeletonJson_readSkeletonDataFile(self.as_raw_mut(), c_path.as_ptr()) };
 
-        match self.error() {
-            Some(err) => Err(err),
-            None => Ok(try_wrap!(ptr, |raw| SkeletonData::from_raw(raw))?),
-        }
+        let result = match self.error() {
+            Some(message) => Err(Error::Parse {
+                file: path.to_owned(),
+                message,
+            }),
+            None => try_wrap!(ptr, "spSkeletonData", |raw| {
+                let data = SkeletonData::from_raw(raw).with_loader(self.loader.take());
+                match self.atlas.take() {
+                    Some(atlas) => data.with_atlas(atlas),
+                    None => data,
+                }
+            }),
+        };
+        // after wrapping, so unwinding disposes the data
+        resume_panic();
+
+        result
+    }
+
+    pub fn read_skeleton_file_with_report(
+        mut self,
+        path: &str,
+    ) -> Result<(SkeletonData, Report), Error> {
+        let missing_regions = self.missing_regions.take();
+        let data = self.read_skeleton_file(path)?;
+        let report = Report {
+            missing_regions: missing_regions
+                .map(|missing| missing.borrow_mut().drain(..).collect())
+                .unwrap_or_default(),
+        };
+
+        Ok((data, report))
     }
 }
 
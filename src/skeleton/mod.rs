@@ -1,14 +1,17 @@
 use animation::Animation;
-use common::from_raw_buf;
+use bone::Bone;
+use common::{from_raw_buf, from_raw_buf_or_empty};
 use libspine_sys::*;
 use raw::*;
 use skeleton::data::Data;
 use slot::Slot;
+use std::ffi::CString;
 use std::ptr::NonNull;
 use std::rc::Rc;
 
 pub mod data;
 pub mod json;
+pub mod snapshot;
 
 pub struct Skeleton {
     data: Rc<Data>,
@@ -22,7 +25,7 @@ impl Skeleton {
     pub fn from_data(data: Rc<Data>) -> Result<Self, Error> {
         let ptr = unsafe { spSkeleton_create(data.as_raw() as *const _ as *mut spSkeletonData) };
 
-        try_wrap!(ptr, |raw| Skeleton { data, raw })
+        try_wrap!(ptr, "spSkeleton", |raw| Skeleton { data, raw })
     }
 
     pub fn animations(&self) -> Vec<Animation> {
@@ -35,6 +38,32 @@ impl Skeleton {
         }
     }
 
+    pub fn bones(&self) -> Vec<Bone> {
+        let raw = self.as_raw();
+        let bones_raw = unsafe { from_raw_buf_or_empty(raw.bones, raw.bonesCount) };
+
+        bones_raw
+            .iter()
+            .filter_map(|p| NonNull::new(*p).map(Bone::from_raw))
+            .collect()
+    }
+
+    pub fn find_bone(&self, name: &str) -> Option<Bone> {
+        let c_name = CString::new(name).ok()?;
+        let ptr = unsafe { spSkeleton_findBone(self.as_raw(), c_name.as_ptr()) };
+
+        NonNull::new(ptr).map(Bone::from_raw)
+    }
+
+    pub fn bone(&self, index: usize) -> Option<Bone> {
+        let raw = self.as_raw();
+        if raw.bones.is_null() || index >= raw.bonesCount.max(0) as usize {
+            return None;
+        }
+
+        NonNull::new(unsafe { *raw.bones.add(index) }).map(Bone::from_raw)
+    }
+
     pub fn slots(&self) -> Vec<Slot> {
         unsafe { self.collect_slots(NonNull::new_unchecked(self.as_raw().slots)) }
     }
@@ -60,13 +89,42 @@ impl Skeleton {
         }
     }
 
+    pub fn set_skin(&mut self, name: &str) -> Result<(), Error> {
+        let c_name = CString::new(name)?;
+        let found = unsafe { spSkeleton_setSkinByName(self.as_raw_mut(), c_name.as_ptr()) };
+
+        if found == 0 {
+            return Err(Error::SkinNotFound(name.to_owned()));
+        }
+
+        Ok(())
+    }
+
+    pub fn set_to_setup_pose(&mut self) {
+        unsafe {
+            spSkeleton_setToSetupPose(self.as_raw_mut());
+        }
+    }
+
+    pub fn set_bones_to_setup_pose(&mut self) {
+        unsafe {
+            spSkeleton_setBonesToSetupPose(self.as_raw_mut());
+        }
+    }
+
+    pub fn set_slots_to_setup_pose(&mut self) {
+        unsafe {
+            spSkeleton_setSlotsToSetupPose(self.as_raw_mut());
+        }
+    }
+
     fn collect_slots(&self, slots: NonNull<*mut spSlot>) -> Vec<Slot> {
         let slots_count = self.as_raw().slotsCount as usize;
         let slots_raw = unsafe { from_raw_buf(slots.as_ptr(), slots_count) };
 
         slots_raw
             .iter()
-            .filter_map(|p| NonNull::new(*p).map(|raw| Slot::from_raw(raw)))
+            .filter_map(|p| NonNull::new(*p).map(Slot::from_raw))
             .collect()
     }
 }
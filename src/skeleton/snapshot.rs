@@ -0,0 +1,225 @@
+use super::Skeleton;
+use common::{from_raw_buf_or_empty, spine_calloc, spine_free};
+use libspine_sys::*;
+use raw::*;
+use std::ptr;
+
+#[derive(Debug, Clone, Copy)]
+struct BoneSnapshot {
+    local: [f32; 7],
+    applied: [f32; 7],
+    applied_valid: i32,
+    world: [f32; 6],
+}
+
+#[derive(Debug, Clone)]
+struct SlotSnapshot {
+    attachment: *mut spAttachment,
+    attachment_time: f32,
+    color: spColor,
+    dark_color: Option<spColor>,
+    deform: Vec<f32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    data: *mut spSkeletonData,
+    bones: Vec<BoneSnapshot>,
+    slots: Vec<SlotSnapshot>,
+    draw_order: Vec<i32>,
+    ik_constraints: Vec<(i32, f32)>,
+    transform_constraints: Vec<[f32; 4]>,
+    path_constraints: Vec<[f32; 4]>,
+    skin: *mut spSkin,
+    color: spColor,
+    time: f32,
+    flip: (i32, i32),
+    position: (f32, f32),
+}
+
+impl Skeleton {
+    pub fn snapshot(&self) -> Snapshot {
+        let raw = self.as_raw();
+
+        unsafe {
+            Snapshot {
+                data: raw.data,
+                bones: from_raw_buf_or_empty(raw.bones, raw.bonesCount)
+                    .iter()
+                    .map(|bone| capture_bone(&**bone))
+                    .collect(),
+                slots: from_raw_buf_or_empty(raw.slots, raw.slotsCount)
+                    .iter()
+                    .map(|slot| capture_slot(&**slot))
+                    .collect(),
+                draw_order: from_raw_buf_or_empty(raw.drawOrder, raw.slotsCount)
+                    .iter()
+                    .map(|slot| (*(**slot).data).index)
+                    .collect(),
+                ik_constraints: from_raw_buf_or_empty(raw.ikConstraints, raw.ikConstraintsCount)
+                    .iter()
+                    .map(|ik| ((**ik).bendDirection, (**ik).mix))
+                    .collect(),
+                transform_constraints: from_raw_buf_or_empty(
+                    raw.transformConstraints,
+                    raw.transformConstraintsCount,
+                ).iter()
+                    .map(|c| {
+                        let c = &**c;
+                        [c.rotateMix, c.translateMix, c.scaleMix, c.shearMix]
+                    })
+                    .collect(),
+                path_constraints: from_raw_buf_or_empty(
+                    raw.pathConstraints,
+                    raw.pathConstraintsCount,
+                ).iter()
+                    .map(|c| {
+                        let c = &**c;
+                        [c.position, c.spacing, c.rotateMix, c.translateMix]
+                    })
+                    .collect(),
+                skin: raw.skin,
+                color: raw.color,
+                time: raw.time,
+                flip: (raw.flipX, raw.flipY),
+                position: (raw.x, raw.y),
+            }
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+        {
+            let raw = self.as_raw();
+            if snapshot.data != raw.data
+                || snapshot.bones.len() != raw.bonesCount as usize
+                || snapshot.slots.len() != raw.slotsCount as usize
+            {
+                return Err(Error::SnapshotMismatch);
+            }
+        }
+
+        unsafe {
+            let raw = self.raw.as_mut();
+            raw.skin = snapshot.skin;
+            raw.color = snapshot.color;
+            raw.time = snapshot.time;
+            raw.flipX = snapshot.flip.0;
+            raw.flipY = snapshot.flip.1;
+            raw.x = snapshot.position.0;
+            raw.y = snapshot.position.1;
+
+            for (i, bone) in snapshot.bones.iter().enumerate() {
+                restore_bone(&mut **raw.bones.add(i), bone);
+            }
+
+            // slots after the skeleton time, attachment time is relative to it
+            for (i, slot) in snapshot.slots.iter().enumerate() {
+                restore_slot(&mut **raw.slots.add(i), slot);
+            }
+
+            // stored as slot indices, pointers would belong to the captured skeleton
+            for (i, &index) in snapshot.draw_order.iter().enumerate() {
+                *raw.drawOrder.add(i) = *raw.slots.add(index as usize);
+            }
+
+            for (i, &(bend_direction, mix)) in snapshot.ik_constraints.iter().enumerate() {
+                let ik = &mut **raw.ikConstraints.add(i);
+                ik.bendDirection = bend_direction;
+                ik.mix = mix;
+            }
+
+            for (i, mixes) in snapshot.transform_constraints.iter().enumerate() {
+                let c = &mut **raw.transformConstraints.add(i);
+                c.rotateMix = mixes[0];
+                c.translateMix = mixes[1];
+                c.scaleMix = mixes[2];
+                c.shearMix = mixes[3];
+            }
+
+            for (i, values) in snapshot.path_constraints.iter().enumerate() {
+                let c = &mut **raw.pathConstraints.add(i);
+                c.position = values[0];
+                c.spacing = values[1];
+                c.rotateMix = values[2];
+                c.translateMix = values[3];
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn capture_bone(bone: &spBone) -> BoneSnapshot {
+    BoneSnapshot {
+        local: [
+            bone.x, bone.y, bone.rotation, bone.scaleX, bone.scaleY, bone.shearX, bone.shearY,
+        ],
+        applied: [
+            bone.ax, bone.ay, bone.arotation, bone.ascaleX, bone.ascaleY, bone.ashearX,
+            bone.ashearY,
+        ],
+        applied_valid: bone.appliedValid,
+        world: [bone.a, bone.b, bone.c, bone.d, bone.worldX, bone.worldY],
+    }
+}
+
+fn restore_bone(bone: &mut spBone, snapshot: &BoneSnapshot) {
+    let [x, y, rotation, scale_x, scale_y, shear_x, shear_y] = snapshot.local;
+    bone.x = x;
+    bone.y = y;
+    bone.rotation = rotation;
+    bone.scaleX = scale_x;
+    bone.scaleY = scale_y;
+    bone.shearX = shear_x;
+    bone.shearY = shear_y;
+
+    let [ax, ay, arotation, ascale_x, ascale_y, ashear_x, ashear_y] = snapshot.applied;
+    bone.ax = ax;
+    bone.ay = ay;
+    bone.arotation = arotation;
+    bone.ascaleX = ascale_x;
+    bone.ascaleY = ascale_y;
+    bone.ashearX = ashear_x;
+    bone.ashearY = ashear_y;
+    bone.appliedValid = snapshot.applied_valid;
+
+    let [a, b, c, d, world_x, world_y] = snapshot.world;
+    bone.a = a;
+    bone.b = b;
+    bone.c = c;
+    bone.d = d;
+    bone.worldX = world_x;
+    bone.worldY = world_y;
+}
+
+unsafe fn capture_slot(slot: &spSlot) -> SlotSnapshot {
+    SlotSnapshot {
+        attachment: slot.attachment,
+        attachment_time: spSlot_getAttachmentTime(slot as *const _ as *mut spSlot),
+        color: slot.color,
+        dark_color: slot.darkColor.as_ref().cloned(),
+        deform: from_raw_buf_or_empty(slot.attachmentVertices, slot.attachmentVerticesCount),
+    }
+}
+
+unsafe fn restore_slot(slot: &mut spSlot, snapshot: &SlotSnapshot) {
+    slot.attachment = snapshot.attachment;
+    spSlot_setAttachmentTime(slot, snapshot.attachment_time);
+    slot.color = snapshot.color;
+    if let (Some(dark_color), Some(slot_dark_color)) =
+        (snapshot.dark_color, slot.darkColor.as_mut())
+    {
+        *slot_dark_color = dark_color;
+    }
+
+    let count = snapshot.deform.len();
+    if count > slot.attachmentVerticesCapacity as usize {
+        spine_free(slot.attachmentVertices);
+        slot.attachmentVertices = spine_calloc(count);
+        slot.attachmentVerticesCapacity = count as i32;
+    }
+    if count > 0 {
+        ptr::copy_nonoverlapping(snapshot.deform.as_ptr(), slot.attachmentVertices, count);
+    }
+    slot.attachmentVerticesCount = count as i32;
+}
@@ -0,0 +1,46 @@
+use common::to_string;
+use libspine_sys::*;
+
+#[derive(Debug, Clone)]
+pub struct SkinEntry {
+    pub slot_index: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Skin {
+    pub name: String,
+    pub entries: Vec<SkinEntry>,
+}
+
+impl Skin {
+    pub fn from_raw(raw_ref: &spSkin, slots_count: i32) -> Self {
+        let name = unsafe { to_string(raw_ref.name) };
+        let mut entries = Vec::new();
+
+        for slot_index in 0..slots_count {
+            let mut attachment_index = 0;
+
+            loop {
+                let ptr = unsafe { spSkin_getAttachmentName(raw_ref, slot_index, attachment_index) };
+                if ptr.is_null() {
+                    break;
+                }
+
+                entries.push(SkinEntry {
+                    slot_index,
+                    name: unsafe { to_string(ptr) },
+                });
+                attachment_index += 1;
+            }
+        }
+
+        Skin { name, entries }
+    }
+
+    pub fn attachments_for_slot(&self, slot_index: i32) -> impl Iterator<Item = &SkinEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.slot_index == slot_index)
+    }
+}
@@ -1,8 +1,12 @@
 use attachment::Attachment;
 use bone::Bone;
+use color::Color;
+use common::{to_opt_string, to_string};
 use libspine_sys::*;
 use raw::*;
 
+pub type BlendMode = spBlendMode;
+
 pub struct Slot {
     raw: NonNull<spSlot>,
 }
@@ -16,11 +20,41 @@ impl Slot {
     }
 
     pub fn attachment(&self) -> Option<Attachment> {
-        NonNull::new(self.as_raw().attachment as *mut spAttachment)
-            .map(|raw| Attachment::from_raw(raw))
+        NonNull::new(self.as_raw().attachment).map(Attachment::from_raw)
     }
 
     pub fn bone(&self) -> Option<Bone> {
-        NonNull::new(self.as_raw().bone as *mut spBone).map(|raw| Bone::from_raw(raw))
+        NonNull::new(self.as_raw().bone).map(Bone::from_raw)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SlotData {
+    pub index: i32,
+    pub name: String,
+    pub bone: String,
+    pub attachment: Option<String>,
+    pub color: Color,
+    pub dark_color: Option<Color>,
+    pub blend_mode: BlendMode,
+}
+
+impl<'a> From<&'a spSlotData> for SlotData {
+    fn from(raw_ref: &'a spSlotData) -> Self {
+        unsafe {
+            SlotData {
+                index: raw_ref.index,
+                name: to_string(raw_ref.name),
+                bone: raw_ref
+                    .boneData
+                    .as_ref()
+                    .map(|bone| to_string(bone.name))
+                    .unwrap_or_default(),
+                attachment: to_opt_string(raw_ref.attachmentName),
+                color: Color::from(raw_ref.color),
+                dark_color: raw_ref.darkColor.as_ref().map(|color| Color::from(*color)),
+                blend_mode: raw_ref.blendMode,
+            }
+        }
     }
 }
@@ -0,0 +1,162 @@
+extern crate image;
+extern crate libc;
+extern crate libspine_sys;
+#[macro_use]
+extern crate spiner;
+
+mod common;
+
+use spiner::animation::blend::MixBlend;
+use spiner::animation::state::{State, StateData};
+use spiner::skeleton::data::Data as SkeletonData;
+use spiner::skeleton::Skeleton;
+use std::rc::Rc;
+
+const BONES: [&str; 2] = ["rear_upper_arm", "gun"];
+// degrees, the layering and these expectations round differently
+const EPSILON: f32 = 1e-3;
+
+fn state(data: &Rc<SkeletonData>) -> State {
+    State::from_data(Rc::new(
+        StateData::from_skeleton_data(Rc::clone(data)).unwrap(),
+    ))
+    .unwrap()
+}
+
+fn rotation(skeleton: &Skeleton, bone: &str) -> f32 {
+    skeleton.find_bone(bone).unwrap().rotation()
+}
+
+// the same range the layering wraps the offset into
+fn wrap_degrees(degrees: f32) -> f32 {
+    degrees - ((degrees + 180.) / 360.).floor() * 360.
+}
+
+fn assert_rotation(skeleton: &Skeleton, bone: &str, expected: f32) {
+    let actual = rotation(skeleton, bone);
+    assert!(
+        wrap_degrees(actual - expected).abs() < EPSILON,
+        "{}: {} != {}",
+        bone,
+        actual,
+        expected
+    );
+}
+
+// `shoot` posed alone from the setup pose at `time`
+fn layer_pose(data: &Rc<SkeletonData>, time: f32) -> Skeleton {
+    let mut skeleton = Skeleton::from_data(Rc::clone(data)).unwrap();
+    let mut state = state(data);
+    state
+        .set_animation_by_name(0, "shoot", false)
+        .unwrap()
+        .set_track_time(time);
+    skeleton.set_bones_to_setup_pose();
+    state.apply(&mut skeleton).unwrap();
+
+    skeleton
+}
+
+// `walk` on track 0, `shoot` on track 1 with `blend` and `alpha`, both advanced by `elapsed`
+fn layered(
+    data: &Rc<SkeletonData>,
+    blend: MixBlend,
+    alpha: f32,
+    elapsed: f32,
+) -> (Skeleton, Skeleton, f32) {
+    let mut base = Skeleton::from_data(Rc::clone(data)).unwrap();
+    let mut base_state = state(data);
+    base_state.set_animation_by_name(0, "walk", true).unwrap();
+    base_state.update(elapsed);
+    base_state.apply(&mut base).unwrap();
+
+    let mut skeleton = Skeleton::from_data(Rc::clone(data)).unwrap();
+    let mut state = state(data);
+    state.set_animation_by_name(0, "walk", true).unwrap();
+    {
+        let mut layer = state.set_animation_by_name(1, "shoot", false).unwrap();
+        layer.set_mix_blend(blend);
+        layer.set_alpha(alpha);
+    }
+    state.update(elapsed);
+    state.apply(&mut skeleton).unwrap();
+    let time = state.current(1).unwrap().animation_time();
+
+    (base, skeleton, time)
+}
+
+#[test]
+fn add_offsets_base_by_layer_times_alpha() {
+    let data = common::spineboy();
+    let alpha = 0.5;
+    let (base, skeleton, time) = layered(&data, MixBlend::Add, alpha, 0.1);
+    let layer = layer_pose(&data, time);
+
+    for bone in BONES.iter() {
+        let setup = data.find_bone(bone).unwrap().rotation;
+        let offset = wrap_degrees(rotation(&layer, bone) - setup);
+        assert!(
+            offset != 0.,
+            "{} is not keyed by the layer at {}",
+            bone,
+            time
+        );
+
+        assert_rotation(&skeleton, bone, rotation(&base, bone) + offset * alpha);
+    }
+}
+
+#[test]
+fn setup_mixes_layer_from_setup_pose() {
+    let data = common::spineboy();
+    let alpha = 0.5;
+    let (_, skeleton, time) = layered(&data, MixBlend::Setup, alpha, 0.1);
+    let layer = layer_pose(&data, time);
+
+    for bone in BONES.iter() {
+        let setup = data.find_bone(bone).unwrap().rotation;
+        let offset = wrap_degrees(rotation(&layer, bone) - setup);
+        assert_rotation(&skeleton, bone, setup + offset * alpha);
+    }
+}
+
+#[test]
+fn first_mixes_layer_from_current_pose() {
+    let data = common::spineboy();
+    let alpha = 0.5;
+    let (base, skeleton, time) = layered(&data, MixBlend::First, alpha, 0.1);
+    let layer = layer_pose(&data, time);
+
+    for bone in BONES.iter() {
+        let current = rotation(&base, bone);
+        let offset = wrap_degrees(rotation(&layer, bone) - current);
+        assert_rotation(&skeleton, bone, current + offset * alpha);
+    }
+}
+
+#[test]
+fn add_at_zero_alpha_keeps_base() {
+    let data = common::spineboy();
+    let (base, skeleton, _) = layered(&data, MixBlend::Add, 0., 0.1);
+
+    for bone in BONES.iter() {
+        assert!(
+            rotation(&skeleton, bone) == rotation(&base, bone),
+            "{}",
+            bone
+        );
+    }
+}
+
+#[test]
+fn mix_blend_is_kept_on_the_entry() {
+    let data = common::spineboy();
+    let mut state = state(&data);
+    let mut entry = state.set_animation_by_name(1, "shoot", false).unwrap();
+    assert_eq!(entry.mix_blend(), MixBlend::Replace);
+
+    for &blend in [MixBlend::Setup, MixBlend::First, MixBlend::Add].iter() {
+        entry.set_mix_blend(blend);
+        assert_eq!(entry.mix_blend(), blend);
+    }
+}
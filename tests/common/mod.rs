@@ -0,0 +1,48 @@
+// shared by the integration tests, each of them uses a subset
+#![allow(dead_code)]
+
+use image;
+use spiner::cache::AssetCache;
+use spiner::extension::Texture;
+use spiner::skeleton::data::Data as SkeletonData;
+use spiner::Error;
+use std::fs::File;
+use std::io::Read;
+use std::rc::Rc;
+
+fn read_file(path: &str) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::new();
+    File::open(path).and_then(|mut f| f.read_to_end(&mut buf))?;
+
+    Ok(buf)
+}
+
+fn read_texture(path: &str, texture: &mut Texture) -> Result<(), Error> {
+    let image = image::open(path)
+        .map_err(|err| Error::TextureLoad {
+            path: path.to_owned(),
+            message: err.to_string(),
+        })?
+        .to_rgba();
+    let (width, height) = image.dimensions();
+    texture.buffer = image.into_raw();
+    texture.width = width;
+    texture.height = height;
+
+    Ok(())
+}
+
+extend_spine!({
+    _spUtil_readFile -> read_file,
+    _spAtlasPage_createTexture -> read_texture
+});
+
+pub fn spineboy() -> Rc<SkeletonData> {
+    AssetCache::new()
+        .skeleton_data(
+            "assets/spineboy/spineboy.json",
+            "assets/spineboy/spineboy.atlas",
+            1.,
+        )
+        .unwrap()
+}
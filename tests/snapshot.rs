@@ -0,0 +1,147 @@
+extern crate image;
+extern crate libc;
+extern crate libspine_sys;
+#[macro_use]
+extern crate spiner;
+
+mod common;
+
+use spiner::animation::events::{AnimationEvent, EventType};
+use spiner::animation::state::{State, StateData};
+use spiner::attachment::vertex::Vertex;
+use spiner::attachment::Attachment;
+use spiner::skeleton::data::Data as SkeletonData;
+use spiner::skeleton::Skeleton;
+use spiner::slot::Slot;
+use std::rc::Rc;
+
+const DELTA: f32 = 1. / 60.;
+
+fn setup() -> (Skeleton, State) {
+    setup_with(common::spineboy())
+}
+
+fn setup_with(data: Rc<SkeletonData>) -> (Skeleton, State) {
+    let skeleton = Skeleton::from_data(Rc::clone(&data)).unwrap();
+    let mut state_data = StateData::from_skeleton_data(data).unwrap();
+    state_data.set_default_mix(0.2);
+    let state = State::from_data(Rc::new(state_data)).unwrap();
+
+    (skeleton, state)
+}
+
+fn step(state: &mut State, skeleton: &mut Skeleton) {
+    state.update(DELTA);
+    state.apply(skeleton).unwrap();
+    skeleton.update_world_transform();
+}
+
+fn world_vertices(skeleton: &Skeleton) -> Vec<Vec<[f32; 2]>> {
+    slot_vertices(&skeleton.slots())
+}
+
+fn slot_vertices(slots: &[Slot]) -> Vec<Vec<[f32; 2]>> {
+    slots
+        .iter()
+        .map(|slot| match (slot.attachment(), slot.bone()) {
+            (Some(Attachment::Region(ref region)), Some(bone)) => region.world_vertices(&bone),
+            (Some(Attachment::Mesh(ref mesh)), _) | (Some(Attachment::LinkedMesh(ref mesh)), _) => {
+                mesh.world_vertices(slot)
+            }
+            (Some(Attachment::BoundingBox(ref bounding_box)), _) => {
+                bounding_box.world_vertices(slot)
+            }
+            (Some(Attachment::Clipping(ref clipping)), _) => clipping.world_vertices(slot),
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+fn kinds(events: &[AnimationEvent]) -> Vec<(EventType, i32, String)> {
+    events
+        .iter()
+        .map(|event| (event.kind, event.track_index, event.animation.to_string()))
+        .collect()
+}
+
+#[test]
+fn restore_replays_world_vertices_exactly() {
+    let (mut skeleton, mut state) = setup();
+    state.set_animation_by_name(0, "walk", true).unwrap();
+    state.add_animation_by_name(0, "run", true, 0.3).unwrap();
+    state.set_animation_by_name(1, "shoot", false).unwrap();
+
+    // the snapshot is taken while walk mixes into run
+    for _ in 0..25 {
+        step(&mut state, &mut skeleton);
+    }
+    state.drain_events().for_each(drop);
+    let skeleton_snapshot = skeleton.snapshot();
+    let state_snapshot = state.snapshot();
+
+    let mut expected = Vec::new();
+    for _ in 0..60 {
+        step(&mut state, &mut skeleton);
+        expected.push(world_vertices(&skeleton));
+    }
+
+    skeleton.restore(&skeleton_snapshot).unwrap();
+    state.restore(&state_snapshot).unwrap();
+
+    for (frame, vertices) in expected.iter().enumerate() {
+        step(&mut state, &mut skeleton);
+        assert!(
+            world_vertices(&skeleton) == *vertices,
+            "world vertices differ {} frames after the restore",
+            frame + 1
+        );
+    }
+}
+
+#[test]
+fn restore_keeps_pending_events_only() {
+    let (mut skeleton, mut state) = setup();
+    state.set_animation_by_name(0, "walk", true).unwrap();
+    step(&mut state, &mut skeleton);
+    state.drain_events().for_each(drop);
+
+    state.set_animation_by_name(0, "run", true).unwrap();
+    step(&mut state, &mut skeleton);
+    let snapshot = state.snapshot();
+    let pending = kinds(&state.drain_events().collect::<Vec<_>>());
+    assert!(!pending.is_empty());
+
+    state.set_animation_by_name(0, "jump", false).unwrap();
+    step(&mut state, &mut skeleton);
+    state.restore(&snapshot).unwrap();
+
+    assert_eq!(kinds(&state.drain_events().collect::<Vec<_>>()), pending);
+}
+
+#[test]
+fn restore_into_another_skeleton_outlives_the_source() {
+    let data = common::spineboy();
+    let (mut source, mut state) = setup_with(Rc::clone(&data));
+    state.set_animation_by_name(0, "walk", true).unwrap();
+    for _ in 0..10 {
+        step(&mut state, &mut source);
+    }
+    let snapshot = source.snapshot();
+    drop(source);
+
+    let mut skeleton = Skeleton::from_data(data).unwrap();
+    skeleton.restore(&snapshot).unwrap();
+
+    // pose the target differently so stale draw order slots would stand out
+    state.set_animation_by_name(0, "run", true).unwrap();
+    for _ in 0..10 {
+        step(&mut state, &mut skeleton);
+    }
+
+    let vertices = world_vertices(&skeleton);
+    let ordered = slot_vertices(&skeleton.slots_ordered());
+    assert_eq!(ordered.len(), vertices.len());
+    for slot in &ordered {
+        assert!(vertices.contains(slot), "draw order holds a foreign slot");
+    }
+}
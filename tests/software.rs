@@ -0,0 +1,49 @@
+extern crate image;
+extern crate libc;
+extern crate libspine_sys;
+#[macro_use]
+extern crate spiner;
+
+mod common;
+
+use libspine_sys::spBlendMode::SP_BLEND_MODE_NORMAL;
+use spiner::math::Affine;
+use spiner::render::software::{draw_triangles, Camera, Canvas};
+use spiner::render::Vertex;
+use spiner::Error;
+
+fn vertex(x: f32, y: f32, color: [f32; 4]) -> Vertex {
+    Vertex {
+        position: [x, y],
+        uv: [0., 0.],
+        color,
+    }
+}
+
+#[test]
+fn untextured_triangle_is_tinted_by_vertex_color() {
+    let mut canvas = Canvas::new(8, 8).unwrap();
+    let camera = Camera {
+        view: Affine::identity(),
+    };
+    let tint = [1., 0.5, 0., 0.5];
+    let vertices = [vertex(0., 0., tint), vertex(8., 0., tint), vertex(0., 8., tint)];
+
+    draw_triangles(&vertices, &[0, 1, 2], None, SP_BLEND_MODE_NORMAL, &camera, &mut canvas);
+
+    // the canvas keeps straight alpha, the tint comes back unpremultiplied
+    assert_eq!(canvas.pixel(1, 1), Some([255, 128, 0, 128]));
+    assert_eq!(canvas.pixel(6, 0), Some([255, 128, 0, 128]));
+    // pixel centers past the hypotenuse stay clear
+    assert_eq!(canvas.pixel(7, 7), Some([0; 4]));
+    assert_eq!(canvas.pixel(4, 4), Some([0; 4]));
+    assert_eq!(canvas.pixel(8, 0), None);
+}
+
+#[test]
+fn oversized_canvas_is_an_error() {
+    match Canvas::new(u32::MAX, u32::MAX) {
+        Err(Error::CanvasTooLarge { .. }) => {}
+        other => panic!("expected CanvasTooLarge, got {:?}", other.map(|canvas| canvas.width)),
+    }
+}